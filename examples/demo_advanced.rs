@@ -55,9 +55,8 @@ fn test_query(query: &str, rb: &arrow::record_batch::RecordBatch) {
     
     match parse_jmespath(query) {
         Ok((_rest, expr)) => {
-            match eval_jmespath(&expr, rb) {
-                result => println!("{:?}", result),
-            }
+            let result = eval_jmespath(&expr, rb);
+            println!("{:?}", result);
         }
         Err(e) => {
             println!("Parse Error: {:?}", e);
@@ -18,7 +18,7 @@ fn main() {
         {"name": "Charlie", "age": 35, "scores": [76, 82, 88]}
     ]);
     
-    let rb = json_to_arrow(&data);
+    let _rb = json_to_arrow(&data);
     
     // Query: numbers[?@ > 80] - filter array elements where element > 80
     println!("Query: scores[?@ > 85]");
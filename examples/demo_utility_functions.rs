@@ -26,7 +26,6 @@ fn main() {
     let result = eval_jmespath(&expr, &rb);
     
     if let arrow_jmespath::EvalResult::Int64Array(arr) = result {
-        use arrow::array::Array;
         let reversed: Vec<i64> = (0..arr.len()).map(|i| arr.value(i)).collect();
         println!("Reversed: {:?}\n", reversed);
     }
@@ -36,3 +36,26 @@ pub fn get_list_array<'a>(record_batch: &'a RecordBatch, path: &str) -> &'a List
 pub fn filter_record_batch_fn(record_batch: &RecordBatch, mask: &BooleanArray) -> RecordBatch {
     filter_record_batch(record_batch, mask).unwrap()
 }
+
+/// Position des Werts von Zeile `index` in den Dictionary-Werten
+pub(crate) fn dictionary_key(dict: &dyn arrow::array::AnyDictionaryArray, index: usize) -> usize {
+    use arrow::array::AsArray;
+    use arrow::datatypes::*;
+
+    fn key_at<T: ArrowPrimitiveType>(keys: &dyn Array, index: usize) -> usize {
+        keys.as_primitive::<T>().value(index).as_usize()
+    }
+
+    let keys = dict.keys();
+    match keys.data_type() {
+        DataType::Int8 => key_at::<Int8Type>(keys, index),
+        DataType::Int16 => key_at::<Int16Type>(keys, index),
+        DataType::Int32 => key_at::<Int32Type>(keys, index),
+        DataType::Int64 => key_at::<Int64Type>(keys, index),
+        DataType::UInt8 => key_at::<UInt8Type>(keys, index),
+        DataType::UInt16 => key_at::<UInt16Type>(keys, index),
+        DataType::UInt32 => key_at::<UInt32Type>(keys, index),
+        DataType::UInt64 => key_at::<UInt64Type>(keys, index),
+        other => panic!("Ungültiger Dictionary-Key-Typ: {:?}", other),
+    }
+}
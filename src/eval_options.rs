@@ -0,0 +1,108 @@
+/// How conversion functions (cast, to_number, ...) treat values that cannot be converted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConversionErrorMode {
+    /// Unconvertible values become null (JMESPath semantics)
+    #[default]
+    Null,
+    /// Unconvertible values abort the evaluation
+    Error,
+}
+
+/// How paths treat fields that do not exist in the batch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingFieldMode {
+    /// Missing fields evaluate to null (JMESPath semantics)
+    #[default]
+    Null,
+    /// Missing fields abort the evaluation (strict mode)
+    Error,
+}
+
+/// How float comparisons order NaN and signed zeros
+///
+/// Applies to comparison operators (also on dictionary-encoded columns). Sorting, ranking
+/// and deduplication (`sort`, `sort_by`, `array_sort`, `top_k`, `bottom_k`, `distinct`,
+/// `distinct_by`, `unique`, `group_by`) always use the total order, since IEEE 754 does
+/// not order NaN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatOrdering {
+    /// Arrow total order: NaN is larger than every value, -0.0 < 0.0
+    #[default]
+    TotalOrder,
+    /// IEEE 754: NaN is unordered (only != is true), -0.0 == 0.0
+    Ieee,
+}
+
+/// How min/max/sum/avg treat NaN values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NanAggregation {
+    /// Any NaN makes the aggregate NaN
+    #[default]
+    Propagate,
+    /// NaN values are ignored like nulls
+    Skip,
+}
+
+/// How NaN and ±Infinity are written to JSON
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonFiniteJson {
+    /// As null (JSON has no representation for them)
+    #[default]
+    Null,
+    /// As the strings "NaN", "Infinity" and "-Infinity"
+    String,
+}
+
+/// How Decimal128/Decimal256 values are written to JSON (the same for every row of a column)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecimalJson {
+    /// As strings with all digits of the scale, e.g. "12.3400"
+    #[default]
+    String,
+    /// As JSON numbers (f64, values beyond its precision are rounded)
+    Number,
+}
+
+/// Handling of float special values in comparisons, aggregates and JSON output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FloatPolicy {
+    pub ordering: FloatOrdering,
+    pub nan_aggregation: NanAggregation,
+    pub non_finite_json: NonFiniteJson,
+}
+
+/// Evaluator options
+#[derive(Debug, Clone, Default)]
+pub struct EvalOptions {
+    pub on_conversion_error: ConversionErrorMode,
+    pub on_missing_field: MissingFieldMode,
+    pub float_policy: FloatPolicy,
+    pub decimal_json: DecimalJson,
+}
+
+thread_local! {
+    /// Options of the evaluation running on this thread
+    static EVAL_OPTIONS: std::cell::RefCell<EvalOptions> = std::cell::RefCell::new(EvalOptions::default());
+}
+
+/// Restores the previous options when an evaluation ends (also on panic)
+struct OptionsGuard(Option<EvalOptions>);
+
+impl Drop for OptionsGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            EVAL_OPTIONS.with(|o| *o.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Options of the current evaluation
+pub(crate) fn eval_options() -> EvalOptions {
+    EVAL_OPTIONS.with(|o| o.borrow().clone())
+}
+
+/// Run `f` with `options` as the current options (restored afterwards)
+pub(crate) fn with_eval_options<R>(options: &EvalOptions, f: impl FnOnce() -> R) -> R {
+    let _guard = OptionsGuard(Some(EVAL_OPTIONS.with(|o| o.replace(options.clone()))));
+    f()
+}
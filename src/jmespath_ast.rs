@@ -19,9 +19,11 @@ pub enum JmesPathExpr {
     ExprRef(Box<JmesPathExpr>), // & - expression reference for functions like sort_by
     // Constants
     ConstInt(i32),
+    ConstInt64(i64), // integers outside i32 (JSON literals)
     ConstFloat(f64),
     ConstBool(bool),
     ConstString(String),
+    ConstNull, // `null`
 }
 
 #[derive(Debug, Clone)]
@@ -1,32 +1,63 @@
 use crate::jmespath_ast::*;
+use crate::arrow_utils::dictionary_key;
+use crate::eval_options::{eval_options, with_eval_options, ConversionErrorMode, EvalOptions, FloatOrdering, MissingFieldMode, NanAggregation};
+use crate::json_to_arrow::{json_to_arrow, array_value_to_json};
 use arrow::array::{
     Int32Array, Int64Array, Float32Array, Float64Array, 
-    BooleanArray, StringArray, ListArray, StructArray, Array
+    BooleanArray, StringArray, ListArray, StructArray, Array,
+    ArrayRef, TimestampMicrosecondArray, make_array, AsArray
 };
 use arrow::compute::{and, or, not, filter_record_batch};
 use arrow::compute::kernels::cmp;
 use arrow::record_batch::RecordBatch;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, TimeUnit};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 /// Evaluator Result - unterstützt verschiedene Array-Typen
 #[derive(Debug)]
 pub enum EvalResult<'a> {
     // Integer types
-    Int32Array(&'a Int32Array),
-    Int64Array(&'a Int64Array),
+    Int32Array(Cow<'a, Int32Array>),
+    Int64Array(Cow<'a, Int64Array>),
     
     // Float types
-    Float32Array(&'a Float32Array),
-    Float64Array(&'a Float64Array),
+    Float32Array(Cow<'a, Float32Array>),
+    Float64Array(Cow<'a, Float64Array>),
     
     // String types
-    StringArray(&'a StringArray),
+    StringArray(Cow<'a, StringArray>),
+    
+    // Other primitive numbers (Int8, Int16, UInt8-UInt64, Float16)
+    NumericArray(ArrayRef),
+    
+    // Decimal128/Decimal256 (exact, with precision and scale)
+    DecimalArray(ArrayRef),
+    
+    // Dictionary-encoded columns (e.g. Dictionary<Int32, Utf8>)
+    DictionaryArray(ArrayRef),
+    
+    // Map<Utf8, V> columns (labels, tags)
+    MapArray(ArrayRef),
+    
+    // Struct columns (nested objects, one per row)
+    StructArray(ArrayRef),
+    
+    // All-null column (e.g. a missing field)
+    NullArray(ArrayRef),
     
     // Boolean
     BoolArray(BooleanArray),
     
+    // Temporal types (Timestamp with any unit/timezone, Date32, Date64)
+    TimestampArray(ArrayRef),
+    
+    // Binary
+    BinaryArray(ArrayRef),
+    
     // Complex types
-    ListArray(&'a ListArray),
+    ListArray(Cow<'a, ListArray>),
+    LargeListArray(ArrayRef),
     RecordBatch(RecordBatch),
     
     // Constants
@@ -35,26 +66,201 @@ pub enum EvalResult<'a> {
     FloatConst(f64),
     BoolConst(bool),
     StringConst(String),
+    TimestampConst(i64), // microseconds since epoch (UTC)
+    StructConst(arrow::array::StructArray), // single object, e.g. one row of a RecordBatch
+    DecimalConst(ArrayRef), // single decimal value (one-element Decimal128/256 array)
+    NullConst,
+}
+
+/// Dispatch a generic kernel over every integer and float primitive type
+macro_rules! numeric_kernel {
+    ($func:expr, $data_type:expr, $kernel:ident ( $($arg:expr),* )) => {{
+        use arrow::datatypes::*;
+        match $data_type {
+            DataType::Int8 => $kernel::<Int8Type>($($arg),*),
+            DataType::Int16 => $kernel::<Int16Type>($($arg),*),
+            DataType::Int32 => $kernel::<Int32Type>($($arg),*),
+            DataType::Int64 => $kernel::<Int64Type>($($arg),*),
+            DataType::UInt8 => $kernel::<UInt8Type>($($arg),*),
+            DataType::UInt16 => $kernel::<UInt16Type>($($arg),*),
+            DataType::UInt32 => $kernel::<UInt32Type>($($arg),*),
+            DataType::UInt64 => $kernel::<UInt64Type>($($arg),*),
+            DataType::Float16 => $kernel::<Float16Type>($($arg),*),
+            DataType::Float32 => $kernel::<Float32Type>($($arg),*),
+            DataType::Float64 => $kernel::<Float64Type>($($arg),*),
+            other => panic!("{}() requires a numeric argument, got: {:?}", $func, other),
+        }
+    }};
+}
+
+
+/// Non-batch value a pipe binds to @ (a column or list, a single value, or a single row)
+#[derive(Clone)]
+enum BoundValue {
+    Array(ArrayRef),
+    Scalar(ArrayRef),
+    Row(StructArray),
+}
+
+impl BoundValue {
+    fn to_result<'a>(&self) -> EvalResult<'a> {
+        match self {
+            BoundValue::Array(array) => array_to_result(array.clone()),
+            BoundValue::Scalar(scalar) => scalar_from_array(scalar.as_ref(), 0),
+            BoundValue::Row(row) => EvalResult::StructConst(row.clone()),
+        }
+    }
+}
+
+thread_local! {
+    /// Values bound to @ by the pipes being evaluated, keyed by the schema of the batch
+    /// their right-hand side runs on
+    static BOUND_VALUES: std::cell::RefCell<Vec<(arrow::datatypes::SchemaRef, BoundValue)>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Removes a pipe binding when the right-hand side is done (also on panic)
+struct BindingGuard;
+
+impl Drop for BindingGuard {
+    fn drop(&mut self) {
+        BOUND_VALUES.with(|b| b.borrow_mut().pop());
+    }
+}
+
+/// The value a pipe bound to @ for this batch (None for regular batches)
+fn bound_value(batch: &RecordBatch) -> Option<BoundValue> {
+    let schema = batch.schema();
+    BOUND_VALUES.with(|b| b.borrow().iter().rev().find(|(s, _)| Arc::ptr_eq(s, &schema)).map(|(_, v)| v.clone()))
+}
+
+/// Evaluate `expr` with `value` as its context: batches and struct columns are evaluated
+/// directly, other values are bound to @ of a column-less batch with one row per value
+fn eval_with_value<'a>(expr: &JmesPathExpr, value: EvalResult) -> EvalResult<'a> {
+    let (batch, bound) = match value {
+        EvalResult::RecordBatch(batch) => (batch, None),
+        // A struct column is bound as the batch of its fields
+        EvalResult::StructArray(arr) => (struct_to_batch(arr.as_struct()), None),
+        EvalResult::StructConst(row) => (struct_to_batch(&row), Some(BoundValue::Row(row))),
+        other => match const_to_array(&other) {
+            Some(scalar) => (empty_batch(1), Some(BoundValue::Scalar(scalar))),
+            None => {
+                let array = result_to_array(&other).unwrap_or_else(|| panic!("Pipe cannot bind {:?} to @", other));
+                (empty_batch(array.len()), Some(BoundValue::Array(array)))
+            },
+        },
+    };
+    let _guard = bound.map(|value| {
+        BOUND_VALUES.with(|b| b.borrow_mut().push((batch.schema(), value)));
+        BindingGuard
+    });
+    into_owned(eval_jmespath(expr, &batch))
+}
+
+/// Keep the rows of a bound value where `mask` is true (a filtered-out single value is null)
+fn filter_bound_value<'a>(value: BoundValue, mask: &BooleanArray) -> EvalResult<'a> {
+    let keep = || mask.len() == 1 && mask.is_valid(0) && mask.value(0);
+    match value {
+        BoundValue::Array(array) => array_to_result(arrow::compute::filter(array.as_ref(), mask).unwrap()),
+        BoundValue::Scalar(scalar) if keep() => scalar_from_array(scalar.as_ref(), 0),
+        BoundValue::Row(row) if keep() => EvalResult::StructConst(row),
+        _ => EvalResult::NullConst,
+    }
+}
+
+/// Batch without columns (its own schema instance, so bindings never collide)
+fn empty_batch(num_rows: usize) -> RecordBatch {
+    let options = arrow::record_batch::RecordBatchOptions::new().with_row_count(Some(num_rows));
+    RecordBatch::try_new_with_options(Arc::new(arrow::datatypes::Schema::empty()), vec![], &options).unwrap()
+}
+
+/// Detach a result from the batch it was evaluated on
+fn into_owned<'a>(result: EvalResult) -> EvalResult<'a> {
+    match result {
+        EvalResult::RecordBatch(batch) => EvalResult::RecordBatch(batch),
+        EvalResult::IntConst(v) => EvalResult::IntConst(v),
+        EvalResult::Int64Const(v) => EvalResult::Int64Const(v),
+        EvalResult::FloatConst(v) => EvalResult::FloatConst(v),
+        EvalResult::BoolConst(v) => EvalResult::BoolConst(v),
+        EvalResult::StringConst(v) => EvalResult::StringConst(v),
+        EvalResult::TimestampConst(v) => EvalResult::TimestampConst(v),
+        EvalResult::StructConst(v) => EvalResult::StructConst(v),
+        EvalResult::DecimalConst(v) => EvalResult::DecimalConst(v),
+        EvalResult::NullConst => EvalResult::NullConst,
+        other => array_to_result(result_to_array(&other).unwrap()),
+    }
 }
 
+/// Primitive numeric types without a dedicated EvalResult variant
+fn is_other_numeric(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Int8 | DataType::Int16 | DataType::UInt8 | DataType::UInt16
+        | DataType::UInt32 | DataType::UInt64 | DataType::Float16)
+}
 
-/// Helper: Get column from RecordBatch by path, returns generic array
-fn get_column_by_path<'a>(rb: &'a RecordBatch, path: &[String]) -> &'a dyn Array {
+/// Helper: Get column from RecordBatch by path (borrowed from the batch, owned after a map lookup;
+/// Err with the missing key)
+fn get_column_by_path<'a, 'p>(rb: &'a RecordBatch, path: &'p [String]) -> Result<Cow<'a, ArrayRef>, &'p str> {
     if path.is_empty() {
         panic!("Empty path");
     }
     
-    let mut current: &dyn Array = rb.column(rb.schema().index_of(&path[0]).unwrap());
+    let Ok(index) = rb.schema().index_of(&path[0]) else {
+        return Err(&path[0]);
+    };
+    let mut current = Cow::Borrowed(rb.column(index));
     
     for key in path.iter().skip(1) {
-        let struct_col = current.as_any().downcast_ref::<StructArray>().unwrap();
-        if let DataType::Struct(fields) = struct_col.data_type() {
-            let field_index = fields.iter().position(|f| f.name() == key).unwrap();
-            current = struct_col.column(field_index);
+        // Map columns: per-row key lookup, `*` projects all values of a row
+        if let Some(map) = current.as_any().downcast_ref::<arrow::array::MapArray>() {
+            current = Cow::Owned(if key == "*" { map_entries_list(map, map.values()) } else { map_lookup(map, key) });
+            continue;
         }
+        // Missing fields and fields of non-objects are null (JMESPath semantics)
+        current = match current {
+            Cow::Borrowed(arr) => match arr.as_any().downcast_ref::<StructArray>().and_then(|s| s.column_by_name(key)) {
+                Some(child) => Cow::Borrowed(child),
+                None => return Err(key),
+            },
+            Cow::Owned(arr) => match arr.as_any().downcast_ref::<StructArray>().and_then(|s| s.column_by_name(key)) {
+                Some(child) => Cow::Owned(child.clone()),
+                None => return Err(key),
+            },
+        };
     }
     
-    current
+    Ok(current)
+}
+
+/// Null column for a path that does not exist (panics with MissingFieldMode::Error)
+fn missing_field<'a>(len: usize, path: &[String], key: &str) -> EvalResult<'a> {
+    if eval_options().on_missing_field == MissingFieldMode::Error {
+        panic!("Field '{}' not found (path: {})", key, path.join("."));
+    }
+    EvalResult::NullArray(Arc::new(arrow::array::NullArray::new(len)))
+}
+
+/// Per-row lookup of `key` in a Map<Utf8, V> column (rows without the key become null)
+fn map_lookup(map: &arrow::array::MapArray, key: &str) -> ArrayRef {
+    let keys = arrow::compute::cast(map.keys(), &DataType::Utf8)
+        .unwrap_or_else(|e| panic!("Map keys must be strings, got {:?}: {}", map.keys().data_type(), e));
+    let keys = keys.as_string::<i32>();
+    let offsets = map.value_offsets();
+    let indices: arrow::array::UInt32Array = (0..map.len())
+        .map(|row| {
+            if map.is_null(row) {
+                return None;
+            }
+            (offsets[row] as usize..offsets[row + 1] as usize)
+                .find(|&i| keys.is_valid(i) && keys.value(i) == key)
+                .map(|i| i as u32)
+        })
+        .collect();
+    arrow::compute::take(map.values().as_ref(), &indices, None).unwrap()
+}
+
+/// Per-row list of a map's keys or values (sharing the map's offsets)
+fn map_entries_list(map: &arrow::array::MapArray, entries: &ArrayRef) -> ArrayRef {
+    let field = Arc::new(arrow::datatypes::Field::new("item", entries.data_type().clone(), true));
+    Arc::new(ListArray::new(field, map.offsets().clone(), entries.clone(), map.nulls().cloned()))
 }
 
 /// Evaluator
@@ -63,13 +269,21 @@ pub fn eval_jmespath<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch) -> EvalResult
     eval_jmespath_internal(expr, rb, rb)
 }
 
+/// Evaluator with explicit options
+pub fn eval_jmespath_with_options<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, options: &EvalOptions) -> EvalResult<'a> {
+    with_eval_options(options, || eval_jmespath(expr, rb))
+}
+
 /// Internal evaluator with context support for @ operator
 fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context: &'a RecordBatch) -> EvalResult<'a> {
     match expr {
         // @ returns the current context
         JmesPathExpr::CurrentNode => {
-            // Return the entire context as RecordBatch
-            EvalResult::RecordBatch(context.clone())
+            // Return the entire context as RecordBatch, or the value a pipe bound to @
+            match bound_value(context) {
+                Some(value) => value.to_result(),
+                None => EvalResult::RecordBatch(context.clone()),
+            }
         },
         
         // & creates an expression reference (stored for later evaluation by functions)
@@ -80,244 +294,119 @@ fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context:
         },
         
         JmesPathExpr::Path(parts) => {
-            let col = get_column_by_path(rb, parts);
+            let col = match get_column_by_path(rb, parts) {
+                Ok(Cow::Borrowed(col)) => col.as_ref(),
+                Ok(Cow::Owned(col)) => return array_to_result(col),
+                Err(key) => return missing_field(rb.num_rows(), parts, key),
+            };
             
             // Match on column data type
             match col.data_type() {
-                DataType::Int32 => EvalResult::Int32Array(col.as_any().downcast_ref::<Int32Array>().unwrap()),
-                DataType::Int64 => EvalResult::Int64Array(col.as_any().downcast_ref::<Int64Array>().unwrap()),
-                DataType::Float32 => EvalResult::Float32Array(col.as_any().downcast_ref::<Float32Array>().unwrap()),
-                DataType::Float64 => EvalResult::Float64Array(col.as_any().downcast_ref::<Float64Array>().unwrap()),
-                DataType::Utf8 => EvalResult::StringArray(col.as_any().downcast_ref::<StringArray>().unwrap()),
+                DataType::Int32 => EvalResult::Int32Array(Cow::Borrowed(col.as_any().downcast_ref::<Int32Array>().unwrap())),
+                DataType::Int64 => EvalResult::Int64Array(Cow::Borrowed(col.as_any().downcast_ref::<Int64Array>().unwrap())),
+                DataType::Float32 => EvalResult::Float32Array(Cow::Borrowed(col.as_any().downcast_ref::<Float32Array>().unwrap())),
+                DataType::Float64 => EvalResult::Float64Array(Cow::Borrowed(col.as_any().downcast_ref::<Float64Array>().unwrap())),
+                DataType::Utf8 => EvalResult::StringArray(Cow::Borrowed(col.as_any().downcast_ref::<StringArray>().unwrap())),
                 DataType::Boolean => EvalResult::BoolArray(col.as_any().downcast_ref::<BooleanArray>().unwrap().clone()),
-                DataType::List(_) => EvalResult::ListArray(col.as_any().downcast_ref::<ListArray>().unwrap()),
+                DataType::List(_) => EvalResult::ListArray(Cow::Borrowed(col.as_any().downcast_ref::<ListArray>().unwrap())),
+                DataType::LargeList(_) => EvalResult::LargeListArray(make_array(col.to_data())),
+                DataType::FixedSizeList(_, _) | DataType::ListView(_) | DataType::LargeListView(_) => {
+                    array_to_result(make_array(col.to_data()))
+                },
+                DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => EvalResult::TimestampArray(make_array(col.to_data())),
+                DataType::Binary => EvalResult::BinaryArray(make_array(col.to_data())),
+                DataType::LargeUtf8 | DataType::Utf8View | DataType::LargeBinary | DataType::BinaryView => {
+                    array_to_result(make_array(col.to_data()))
+                },
+                t if is_other_numeric(t) => EvalResult::NumericArray(make_array(col.to_data())),
+                DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => EvalResult::DecimalArray(make_array(col.to_data())),
+                DataType::Dictionary(_, _) => EvalResult::DictionaryArray(make_array(col.to_data())),
+                DataType::Map(_, _) => EvalResult::MapArray(make_array(col.to_data())),
+                DataType::Struct(_) => EvalResult::StructArray(make_array(col.to_data())),
+                DataType::Null => EvalResult::NullArray(make_array(col.to_data())),
                 _ => panic!("Unsupported column type: {:?}", col.data_type()),
             }
         },
         
         JmesPathExpr::ConstInt(v) => EvalResult::IntConst(*v),
+        JmesPathExpr::ConstInt64(v) => EvalResult::Int64Const(*v),
         JmesPathExpr::ConstFloat(v) => EvalResult::FloatConst(*v),
         JmesPathExpr::ConstBool(v) => EvalResult::BoolConst(*v),
         JmesPathExpr::ConstString(v) => EvalResult::StringConst(v.clone()),
+        JmesPathExpr::ConstNull => EvalResult::NullConst,
         
         JmesPathExpr::Compare { op, lhs, rhs } => {
-            let lhs_val = eval_jmespath(lhs, rb);
-            let rhs_val = eval_jmespath(rhs, rb);
-            
-            // Int32 comparisons
-            if let (EvalResult::Int32Array(col), EvalResult::IntConst(c)) = (&lhs_val, &rhs_val) {
-                let scalar_value = arrow_array::Scalar::new(Int32Array::from(vec![*c]));
-                let mask = match op {
-                    CompareOp::Eq => cmp::eq(col, &scalar_value).unwrap(),
-                    CompareOp::Ne => cmp::neq(col, &scalar_value).unwrap(),
-                    CompareOp::Gt => cmp::gt(col, &scalar_value).unwrap(),
-                    CompareOp::Lt => cmp::lt(col, &scalar_value).unwrap(),
-                    CompareOp::Gte => cmp::gt_eq(col, &scalar_value).unwrap(),
-                    CompareOp::Lte => cmp::lt_eq(col, &scalar_value).unwrap(),
-                };
-                return EvalResult::BoolArray(mask);
-            }
-            
-            // Int64 comparisons (also accepts Int32 constants)
-            if let (EvalResult::Int64Array(col), EvalResult::IntConst(c)) = (&lhs_val, &rhs_val) {
-                let scalar_value = arrow_array::Scalar::new(Int64Array::from(vec![*c as i64]));
-                let mask = match op {
-                    CompareOp::Eq => cmp::eq(col, &scalar_value).unwrap(),
-                    CompareOp::Ne => cmp::neq(col, &scalar_value).unwrap(),
-                    CompareOp::Gt => cmp::gt(col, &scalar_value).unwrap(),
-                    CompareOp::Lt => cmp::lt(col, &scalar_value).unwrap(),
-                    CompareOp::Gte => cmp::gt_eq(col, &scalar_value).unwrap(),
-                    CompareOp::Lte => cmp::lt_eq(col, &scalar_value).unwrap(),
-                };
-                return EvalResult::BoolArray(mask);
-            }
-            
-            // Float comparisons (handle both Float32 and Float64)
-            if let (EvalResult::Float64Array(col), EvalResult::FloatConst(c)) = (&lhs_val, &rhs_val) {
-                let scalar_value = arrow_array::Scalar::new(Float64Array::from(vec![*c]));
-                let mask = match op {
-                    CompareOp::Eq => cmp::eq(col, &scalar_value).unwrap(),
-                    CompareOp::Ne => cmp::neq(col, &scalar_value).unwrap(),
-                    CompareOp::Gt => cmp::gt(col, &scalar_value).unwrap(),
-                    CompareOp::Lt => cmp::lt(col, &scalar_value).unwrap(),
-                    CompareOp::Gte => cmp::gt_eq(col, &scalar_value).unwrap(),
-                    CompareOp::Lte => cmp::lt_eq(col, &scalar_value).unwrap(),
-                };
-                return EvalResult::BoolArray(mask);
-            }
-            
-            // Float32 comparisons
-            if let (EvalResult::Float32Array(col), EvalResult::FloatConst(c)) = (&lhs_val, &rhs_val) {
-                let scalar_value = arrow_array::Scalar::new(Float32Array::from(vec![*c as f32]));
-                let mask = match op {
-                    CompareOp::Eq => cmp::eq(col, &scalar_value).unwrap(),
-                    CompareOp::Ne => cmp::neq(col, &scalar_value).unwrap(),
-                    CompareOp::Gt => cmp::gt(col, &scalar_value).unwrap(),
-                    CompareOp::Lt => cmp::lt(col, &scalar_value).unwrap(),
-                    CompareOp::Gte => cmp::gt_eq(col, &scalar_value).unwrap(),
-                    CompareOp::Lte => cmp::lt_eq(col, &scalar_value).unwrap(),
-                };
-                return EvalResult::BoolArray(mask);
-            }
-            
-            // String comparisons
-            if let (EvalResult::StringArray(col), EvalResult::StringConst(s)) = (&lhs_val, &rhs_val) {
-                let scalar_value = arrow_array::Scalar::new(StringArray::from(vec![s.as_str()]));
-                let mask = match op {
-                    CompareOp::Eq => cmp::eq(col, &scalar_value).unwrap(),
-                    CompareOp::Ne => cmp::neq(col, &scalar_value).unwrap(),
-                    CompareOp::Gt => cmp::gt(col, &scalar_value).unwrap(),
-                    CompareOp::Lt => cmp::lt(col, &scalar_value).unwrap(),
-                    CompareOp::Gte => cmp::gt_eq(col, &scalar_value).unwrap(),
-                    CompareOp::Lte => cmp::lt_eq(col, &scalar_value).unwrap(),
-                };
-                return EvalResult::BoolArray(mask);
-            }
-            
-            // Boolean comparisons
-            if let (EvalResult::BoolArray(col), EvalResult::BoolConst(b)) = (&lhs_val, &rhs_val) {
-                let scalar_value = arrow_array::Scalar::new(BooleanArray::from(vec![*b]));
-                let mask = match op {
-                    CompareOp::Eq => cmp::eq(col, &scalar_value).unwrap(),
-                    CompareOp::Ne => cmp::neq(col, &scalar_value).unwrap(),
-                    _ => panic!("Only == and != supported for boolean comparisons"),
-                };
-                return EvalResult::BoolArray(mask);
-            }
-            
-            // Constant comparisons (Int64Const vs IntConst, etc.)
-            if let (EvalResult::Int64Const(a), EvalResult::IntConst(b)) = (&lhs_val, &rhs_val) {
-                let result = match op {
-                    CompareOp::Eq => a == &(*b as i64),
-                    CompareOp::Ne => a != &(*b as i64),
-                    CompareOp::Gt => a > &(*b as i64),
-                    CompareOp::Lt => a < &(*b as i64),
-                    CompareOp::Gte => a >= &(*b as i64),
-                    CompareOp::Lte => a <= &(*b as i64),
-                };
-                return EvalResult::BoolConst(result);
-            }
-            
-            if let (EvalResult::IntConst(a), EvalResult::Int64Const(b)) = (&lhs_val, &rhs_val) {
-                let result = match op {
-                    CompareOp::Eq => (*a as i64) == *b,
-                    CompareOp::Ne => (*a as i64) != *b,
-                    CompareOp::Gt => (*a as i64) > *b,
-                    CompareOp::Lt => (*a as i64) < *b,
-                    CompareOp::Gte => (*a as i64) >= *b,
-                    CompareOp::Lte => (*a as i64) <= *b,
-                };
-                return EvalResult::BoolConst(result);
-            }
-            
-            // IntConst vs IntConst
-            if let (EvalResult::IntConst(a), EvalResult::IntConst(b)) = (&lhs_val, &rhs_val) {
-                let result = match op {
-                    CompareOp::Eq => a == b,
-                    CompareOp::Ne => a != b,
-                    CompareOp::Gt => a > b,
-                    CompareOp::Lt => a < b,
-                    CompareOp::Gte => a >= b,
-                    CompareOp::Lte => a <= b,
-                };
-                return EvalResult::BoolConst(result);
-            }
-            
-            panic!("Unsupported comparison types: {:?} {:?} {:?}", lhs_val, op, rhs_val);
+            compare_results(op, eval_jmespath(lhs, rb), eval_jmespath(rhs, rb))
         },
         
-        JmesPathExpr::Projection(base_expr, _field_expr) => {
-            // Evaluate the base expression
+        JmesPathExpr::Projection(base_expr, field_expr) => {
+            // The rows of the batch itself are projected with @[*]
             let base_result = eval_jmespath(base_expr, rb);
+            let projects_all = matches!(field_expr.as_ref(), JmesPathExpr::Path(parts) if parts.is_empty());
             
-            // For now, we support projection on ListArray
-            if let EvalResult::ListArray(_list_arr) = base_result {
-                // For each element in the list, evaluate the field expression
-                // This is a simplified implementation
-                panic!("List projection not yet fully implemented");
-            } else {
-                panic!("Projection requires a list/array: got {:?}", base_result);
+            match base_result {
+                EvalResult::RecordBatch(batch) if projects_all => EvalResult::RecordBatch(batch),
+                EvalResult::RecordBatch(batch) => {
+                    // Evaluating the field per row of a batch yields its column
+                    into_owned(eval_jmespath(field_expr, &batch))
+                },
+                EvalResult::ListArray(_) | EvalResult::LargeListArray(_) if projects_all => base_result,
+                EvalResult::ListArray(_) | EvalResult::LargeListArray(_) => {
+                    // Evaluate the field on all elements at once and keep the list layout
+                    let list = list_rows("projection", &base_result);
+                    let Some(elements) = list.values.as_any().downcast_ref::<StructArray>() else {
+                        panic!("Projection of a field requires a list of objects: got {:?}", list.values.data_type());
+                    };
+                    let elements = struct_to_batch(elements);
+                    let projected = eval_jmespath(field_expr, &elements);
+                    let projected = result_to_array(&projected)
+                        .unwrap_or_else(|| panic!("Projection must evaluate to a column, got: {:?}", projected));
+                    let rows = list.rows.iter().map(|r| r.clone().map(|range| range.collect())).collect();
+                    array_to_result(build_list(&list, &projected, rows))
+                },
+                // Flattened lists of objects: the field is evaluated on all elements
+                EvalResult::StructArray(_) if projects_all => base_result,
+                EvalResult::StructArray(arr) => into_owned(eval_jmespath(field_expr, &struct_to_batch(arr.as_struct()))),
+                // Projecting a missing field (null) yields null
+                EvalResult::NullArray(_) | EvalResult::NullConst => base_result,
+                _ => panic!("Projection requires a list/array: got {:?}", base_result),
             }
         },
         
         JmesPathExpr::Slice { base, start, stop, step } => {
             let base_result = eval_jmespath(base, rb);
             
-            match base_result {
-                EvalResult::Int32Array(arr) => {
-                    let values = slice_array(&|i| arr.value(i), arr.len(), *start, *stop, *step);
-                    // Create new array from sliced values
-                    let sliced = Int32Array::from(values);
-                    // We need to leak this to get a 'a lifetime
-                    return EvalResult::Int32Array(Box::leak(Box::new(sliced)));
-                },
-                EvalResult::Int64Array(arr) => {
-                    let values = slice_array(&|i| arr.value(i), arr.len(), *start, *stop, *step);
-                    let sliced = Int64Array::from(values);
-                    return EvalResult::Int64Array(Box::leak(Box::new(sliced)));
-                },
-                EvalResult::StringArray(arr) => {
-                    let values: Vec<&str> = slice_array(&|i| arr.value(i), arr.len(), *start, *stop, *step);
-                    let sliced = StringArray::from(values);
-                    return EvalResult::StringArray(Box::leak(Box::new(sliced)));
-                },
-                _ => panic!("Slice not supported for this type: {:?}", base_result),
+            // Contiguous slices are zero-copy views, stepped slices select rows by index
+            if let EvalResult::RecordBatch(batch) = base_result {
+                return match slice_bounds(batch.num_rows(), *start, *stop, *step) {
+                    Ok((offset, len)) => EvalResult::RecordBatch(batch.slice(offset, len)),
+                    Err(indices) => EvalResult::RecordBatch(take_rows("slice", &batch, &indices)),
+                };
+            }
+            let arr = result_to_array(&base_result)
+                .unwrap_or_else(|| panic!("Slice not supported for this type: {:?}", base_result));
+            match slice_bounds(arr.len(), *start, *stop, *step) {
+                Ok((offset, len)) => array_to_result(arr.slice(offset, len)),
+                Err(indices) => array_to_result(arrow::compute::take(arr.as_ref(), &indices, None).unwrap()),
             }
         },
         
         JmesPathExpr::Index(base, idx) => {
             let base_result = eval_jmespath(base, rb);
             
-            match base_result {
-                EvalResult::Int32Array(arr) => {
-                    let index = normalize_index(*idx, arr.len());
-                    if arr.is_valid(index) {
-                        return EvalResult::IntConst(arr.value(index));
-                    } else {
-                        panic!("Index {} is null", idx);
-                    }
-                },
-                EvalResult::Int64Array(arr) => {
-                    let index = normalize_index(*idx, arr.len());
-                    if arr.is_valid(index) {
-                        return EvalResult::Int64Const(arr.value(index));
-                    } else {
-                        panic!("Index {} is null", idx);
-                    }
-                },
-                EvalResult::Float32Array(arr) => {
-                    let index = normalize_index(*idx, arr.len());
-                    if arr.is_valid(index) {
-                        return EvalResult::FloatConst(arr.value(index) as f64);
-                    } else {
-                        panic!("Index {} is null", idx);
-                    }
-                },
-                EvalResult::Float64Array(arr) => {
-                    let index = normalize_index(*idx, arr.len());
-                    if arr.is_valid(index) {
-                        return EvalResult::FloatConst(arr.value(index));
-                    } else {
-                        panic!("Index {} is null", idx);
-                    }
-                },
-                EvalResult::StringArray(arr) => {
-                    let index = normalize_index(*idx, arr.len());
-                    if arr.is_valid(index) {
-                        return EvalResult::StringConst(arr.value(index).to_string());
-                    } else {
-                        panic!("Index {} is null", idx);
-                    }
-                },
-                EvalResult::BoolArray(arr) => {
-                    let index = normalize_index(*idx, arr.len());
-                    if arr.is_valid(index) {
-                        return EvalResult::BoolConst(arr.value(index));
-                    } else {
-                        panic!("Index {} is null", idx);
-                    }
-                },
-                _ => panic!("Index not supported for this type: {:?}", base_result),
+            // Out-of-range indices yield null (JMESPath semantics)
+            if let EvalResult::RecordBatch(batch) = base_result {
+                // A single row becomes an object
+                return match normalize_index(*idx, batch.num_rows()) {
+                    Some(index) => EvalResult::StructConst(arrow::array::StructArray::from(batch.slice(index, 1))),
+                    None => EvalResult::NullConst,
+                };
+            }
+            let arr = result_to_array(&base_result)
+                .unwrap_or_else(|| panic!("Index not supported for this type: {:?}", base_result));
+            match normalize_index(*idx, arr.len()) {
+                Some(index) => scalar_from_array(arr.as_ref(), index),
+                None => EvalResult::NullConst,
             }
         },
         
@@ -325,49 +414,32 @@ fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context:
             // Evaluate left side first
             let left_result = eval_jmespath(left, rb);
             
-            // If left result is a RecordBatch, use it for right evaluation
-            if let EvalResult::RecordBatch(new_rb) = left_result {
-                // Leak the RecordBatch to get proper lifetime
-                let leaked_rb = Box::leak(Box::new(new_rb));
-                return eval_jmespath(right, leaked_rb);
-            } else {
-                // For other result types, we need to handle differently
-                // For now, we'll create a temporary context
-                panic!("Pipe operator requires RecordBatch result from left side");
+            // The left result becomes the context of the right side; non-batch values
+            // (columns, lists, scalars, single rows) are bound to @.
+            // Projections end here, the right side sees the complete left result.
+            let is_row = matches!(left_result, EvalResult::StructConst(_));
+            let result = eval_with_value(right, left_result);
+            
+            // Fields of a single row are single values
+            match result_to_array(&result) {
+                Some(arr) if is_row && arr.len() == 1 => scalar_from_array(arr.as_ref(), 0),
+                _ => result,
             }
         },
         
         JmesPathExpr::Flatten(base_expr) => {
             let base_result = eval_jmespath(base_expr, rb);
             
-            // Flatten only works on ListArray
-            if let EvalResult::ListArray(list_arr) = base_result {
-                // Flatten the list array by concatenating all inner arrays
-                let mut all_values = Vec::new();
-                for i in 0..list_arr.len() {
-                    if list_arr.is_valid(i) {
-                        let inner_arr = list_arr.value(i);
-                        // Assuming inner array is Int64Array for now
-                        if let Some(int_arr) = inner_arr.as_any().downcast_ref::<Int64Array>() {
-                            for j in 0..int_arr.len() {
-                                if int_arr.is_valid(j) {
-                                    all_values.push(int_arr.value(j));
-                                }
-                            }
-                        }
-                    }
-                }
-                let flattened = Int64Array::from(all_values);
-                return EvalResult::Int64Array(Box::leak(Box::new(flattened)));
-            } else {
+            // Flatten works on ListArray/LargeListArray of any child type (null elements are dropped)
+            if !matches!(base_result, EvalResult::ListArray(_) | EvalResult::LargeListArray(_)) {
                 panic!("Flatten requires a list/array: got {:?}", base_result);
             }
+            array_to_result(list_elements("flatten", &base_result))
         },
         
         JmesPathExpr::MultiSelectHash(pairs) => {
             // Create a new RecordBatch with selected fields
             use arrow::datatypes::{Schema, Field};
-            use std::sync::Arc;
             
             let mut fields = Vec::new();
             let mut columns: Vec<Arc<dyn Array>> = Vec::new();
@@ -375,31 +447,25 @@ fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context:
             for (key, expr) in pairs {
                 let result = eval_jmespath(expr, rb);
                 
+                // Nested multi-selects (and other batches) become struct columns
+                let result = match result {
+                    EvalResult::RecordBatch(batch) => EvalResult::StructArray(Arc::new(StructArray::from(batch))),
+                    other => other,
+                };
+                
                 // Convert result to array
-                match result {
-                    EvalResult::Int32Array(arr) => {
-                        fields.push(Field::new(key, DataType::Int32, true));
-                        columns.push(Arc::new(arr.clone()) as Arc<dyn Array>);
-                    },
-                    EvalResult::Int64Array(arr) => {
-                        fields.push(Field::new(key, DataType::Int64, true));
-                        columns.push(Arc::new(arr.clone()) as Arc<dyn Array>);
+                match result_to_array(&result) {
+                    Some(arr) => {
+                        fields.push(Field::new(key, arr.data_type().clone(), true));
+                        columns.push(arr);
                     },
-                    EvalResult::StringArray(arr) => {
-                        fields.push(Field::new(key, DataType::Utf8, true));
-                        columns.push(Arc::new(arr.clone()) as Arc<dyn Array>);
-                    },
-                    EvalResult::BoolArray(arr) => {
-                        fields.push(Field::new(key, DataType::Boolean, true));
-                        columns.push(Arc::new(arr) as Arc<dyn Array>);
-                    },
-                    _ => panic!("Unsupported result type for multi-select: {:?}", result),
+                    None => panic!("Unsupported result type for multi-select: {:?}", result),
                 }
             }
             
             let schema = Arc::new(Schema::new(fields));
             let new_rb = RecordBatch::try_new(schema, columns).unwrap();
-            return EvalResult::RecordBatch(new_rb);
+            EvalResult::RecordBatch(new_rb)
         },
         
         JmesPathExpr::MultiSelectList(_exprs) => {
@@ -410,19 +476,23 @@ fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context:
         JmesPathExpr::Filter(_array_expr, cond_expr) => {
             // Evaluate array expression - should be a path to the RecordBatch
             // For now, we assume the filter is applied to the entire RecordBatch
-            let condition_result = eval_jmespath(cond_expr, rb);
+            // Rows whose condition is not truthy (false, null, empty) are dropped
+            let mask = match truthy(eval_jmespath(cond_expr, rb)) {
+                EvalResult::BoolArray(mask) => mask,
+                EvalResult::BoolConst(keep) => BooleanArray::from(vec![keep; rb.num_rows()]),
+                other => panic!("Filter condition must evaluate to booleans, got {:?}", other),
+            };
             
-            if let EvalResult::BoolArray(mask) = condition_result {
-                // Filter the RecordBatch using the boolean mask
-                let filtered = filter_record_batch(rb, &mask).unwrap();
-                return EvalResult::RecordBatch(filtered);
-            } else {
-                panic!("Filter condition must evaluate to BoolArray");
+            // Filter the value a pipe bound to @, otherwise the RecordBatch
+            match bound_value(rb) {
+                Some(value) => filter_bound_value(value, &mask),
+                None => EvalResult::RecordBatch(filter_record_batch(rb, &mask).unwrap()),
             }
         },
         
         JmesPathExpr::Logic { op, lhs, rhs } => {
-            let l = eval_jmespath(lhs, rb);
+            // Null operands are falsy, so logic results are never null
+            let l = truthy(eval_jmespath(lhs, rb));
             
             match op {
                 LogicOp::Not => {
@@ -434,7 +504,7 @@ fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context:
                     panic!("NOT requires boolean operand");
                 },
                 LogicOp::And | LogicOp::Or => {
-                    let r = rhs.as_ref().map(|r| eval_jmespath(r, rb)).expect("AND/OR require two operands");
+                    let r = rhs.as_ref().map(|r| truthy(eval_jmespath(r, rb))).expect("AND/OR require two operands");
                     
                     match (l, r) {
                         (EvalResult::BoolArray(a), EvalResult::BoolArray(b)) => {
@@ -471,6 +541,9 @@ fn eval_jmespath_internal<'a>(expr: &JmesPathExpr, rb: &'a RecordBatch, context:
 
 /// Evaluate a function call
 fn eval_function<'a>(name: &str, args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if let Some(result) = eval_dictionary_function(name, args, rb) {
+        return result;
+    }
     match name {
         "length" => func_length(args, rb),
         "contains" => func_contains(args, rb),
@@ -481,14 +554,83 @@ fn eval_function<'a>(name: &str, args: &[JmesPathExpr], rb: &'a RecordBatch) ->
         "max" => func_max(args, rb),
         "sum" => func_sum(args, rb),
         "avg" => func_avg(args, rb),
+        "count" => func_count(args, rb),
+        "count_distinct" => func_count_distinct(args, rb),
+        "median" => func_median(args, rb),
+        "percentile" => func_percentile(args, rb),
+        "variance" => func_variance(args, rb),
+        "stddev" => func_stddev(args, rb),
+        "mode" => func_mode(args, rb),
+        "if" => func_if(args, rb),
+        "case" => func_case(args, rb),
+        "coalesce" => func_coalesce(args, rb),
+        "if_null" => func_if_null(args, rb),
+        "nullif" => func_nullif(args, rb),
+        "is_null" => func_is_null(args, rb),
+        "to_number" => func_to_number(args, rb),
+        "to_boolean" => func_to_boolean(args, rb),
+        "cast" => func_cast(args, rb),
         "abs" => func_abs(args, rb),
+        "ceil" => func_ceil(args, rb),
+        "floor" => func_floor(args, rb),
+        "round" => func_round(args, rb),
+        "sqrt" => func_sqrt(args, rb),
+        "pow" => func_pow(args, rb),
+        "log" => func_log(args, rb),
+        "exp" => func_exp(args, rb),
+        "sign" => func_sign(args, rb),
+        "clamp" => func_clamp(args, rb),
+        "mod" => func_mod(args, rb),
         "keys" => func_keys(args, rb),
         "values" => func_values(args, rb),
         "type" => func_type(args, rb),
         "sort_by" => func_sort_by(args, rb),
         "group_by" => func_group_by(args, rb),
+        "top_k" => func_top_k(args, rb),
+        "bottom_k" => func_bottom_k(args, rb),
+        "distinct" => func_distinct(args, rb),
+        "distinct_by" => func_distinct_by(args, rb),
+        "unnest" => func_unnest(args, rb),
+        "explode" => func_explode(args, rb),
+        "pivot" => func_pivot(args, rb),
+        "limit" => func_limit(args, rb),
+        "offset" => func_offset(args, rb),
+        "sample" => func_sample(args, rb),
+        "sample_fraction" => func_sample_fraction(args, rb),
+        "with_columns" => func_with_columns(args, rb),
+        "row_number" => func_row_number(args, rb),
+        "rank" => func_rank(args, rb),
+        "dense_rank" => func_dense_rank(args, rb),
+        "lag" => func_lag(args, rb),
+        "lead" => func_lead(args, rb),
+        "running_sum" => func_running_sum(args, rb),
+        "unpivot" => func_unpivot(args, rb),
         "reverse" => func_reverse(args, rb),
+        "sort" => func_sort(args, rb),
         "not_null" => func_not_null(args, rb),
+        "now" => func_now(args, rb),
+        "to_timestamp" => func_to_timestamp(args, rb),
+        "date_trunc" => func_date_trunc(args, rb),
+        "extract" => func_extract(args, rb),
+        "ts_diff" => func_ts_diff(args, rb),
+        "parse_json" => func_parse_json(args, rb),
+        "to_json" => func_to_json(args, rb),
+        "json_extract" => func_json_extract(args, rb),
+        "md5" => func_md5(args, rb),
+        "sha256" => func_sha256(args, rb),
+        "xxhash64" => func_xxhash64(args, rb),
+        "hash_bucket" => func_hash_bucket(args, rb),
+        "base64_encode" => func_base64_encode(args, rb),
+        "base64_decode" => func_base64_decode(args, rb),
+        "hex" => func_hex(args, rb),
+        "unique" => func_unique(args, rb),
+        "array_contains" => func_array_contains(args, rb),
+        "array_position" => func_array_position(args, rb),
+        "array_concat" => func_array_concat(args, rb),
+        "array_slice" => func_array_slice(args, rb),
+        "array_sort" => func_array_sort(args, rb),
+        "array_sum" => func_array_sum(args, rb),
+        "array_length" => func_array_length(args, rb),
         _ => panic!("Unknown function: {}", name),
     }
 }
@@ -500,22 +642,26 @@ fn func_length<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a>
     }
     
     let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
     
     match arg {
         EvalResult::StringArray(arr) => {
             // String length for each element
-            let lengths: Vec<i32> = (0..arr.len())
-                .map(|i| if arr.is_null(i) { 0 } else { arr.value(i).len() as i32 })
-                .collect();
-            let result = Int32Array::from(lengths);
-            // We need to return a reference, so we leak it (not ideal but works for demo)
-            EvalResult::Int32Array(Box::leak(Box::new(result)))
+            // Null strings stay null
+            let result: Int32Array = arr.iter().map(|v| v.map(|s| s.len() as i32)).collect();
+            EvalResult::Int32Array(Cow::Owned(result))
         },
-        EvalResult::ListArray(_arr) => {
-            // Array length
-            // For now, return constant
-            // TODO: Implement proper list length
-            panic!("length() for arrays not yet fully implemented");
+        EvalResult::BinaryArray(arr) => {
+            // Byte count for each element (null stays null)
+            let binary = arr.as_binary::<i32>();
+            let result: Int32Array = binary.iter().map(|v| v.map(|b| b.len() as i32)).collect();
+            EvalResult::Int32Array(Cow::Owned(result))
+        },
+        EvalResult::ListArray(_) | EvalResult::LargeListArray(_) => {
+            // Number of elements per row (null lists stay null)
+            list_lengths("length", &arg)
         },
         EvalResult::StringConst(s) => {
             EvalResult::IntConst(s.len() as i32)
@@ -532,12 +678,13 @@ fn func_contains<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'
     
     let haystack = eval_jmespath(&args[0], rb);
     let needle = eval_jmespath(&args[1], rb);
+    if let Some(null) = null_propagation(&[&haystack, &needle]) {
+        return null;
+    }
     
     if let (EvalResult::StringArray(arr), EvalResult::StringConst(search)) = (haystack, needle) {
-        let results: Vec<bool> = (0..arr.len())
-            .map(|i| if arr.is_null(i) { false } else { arr.value(i).contains(&search) })
-            .collect();
-        let result = BooleanArray::from(results);
+        // Null strings stay null
+        let result: BooleanArray = arr.iter().map(|v| v.map(|s| s.contains(&search))).collect();
         return EvalResult::BoolArray(result);
     }
     
@@ -552,12 +699,13 @@ fn func_starts_with<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResul
     
     let haystack = eval_jmespath(&args[0], rb);
     let prefix = eval_jmespath(&args[1], rb);
+    if let Some(null) = null_propagation(&[&haystack, &prefix]) {
+        return null;
+    }
     
     if let (EvalResult::StringArray(arr), EvalResult::StringConst(search)) = (haystack, prefix) {
-        let results: Vec<bool> = (0..arr.len())
-            .map(|i| if arr.is_null(i) { false } else { arr.value(i).starts_with(&search) })
-            .collect();
-        let result = BooleanArray::from(results);
+        // Null strings stay null
+        let result: BooleanArray = arr.iter().map(|v| v.map(|s| s.starts_with(&search))).collect();
         return EvalResult::BoolArray(result);
     }
     
@@ -572,44 +720,27 @@ fn func_ends_with<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<
     
     let haystack = eval_jmespath(&args[0], rb);
     let suffix = eval_jmespath(&args[1], rb);
+    if let Some(null) = null_propagation(&[&haystack, &suffix]) {
+        return null;
+    }
     
     if let (EvalResult::StringArray(arr), EvalResult::StringConst(search)) = (haystack, suffix) {
-        let results: Vec<bool> = (0..arr.len())
-            .map(|i| if arr.is_null(i) { false } else { arr.value(i).ends_with(&search) })
-            .collect();
-        let result = BooleanArray::from(results);
+        // Null strings stay null
+        let result: BooleanArray = arr.iter().map(|v| v.map(|s| s.ends_with(&search))).collect();
         return EvalResult::BoolArray(result);
     }
     
     panic!("ends_with() requires (string_array, string_const) arguments");
 }
 
-/// to_string(value) - converts value to string
+/// to_string(value) - converts value to string (nulls stay null)
 fn func_to_string<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
     if args.len() != 1 {
         panic!("to_string() requires exactly 1 argument");
     }
     
     let arg = eval_jmespath(&args[0], rb);
-    
-    match arg {
-        EvalResult::Int32Array(arr) => {
-            let strings: Vec<String> = (0..arr.len())
-                .map(|i| if arr.is_null(i) { "".to_string() } else { arr.value(i).to_string() })
-                .collect();
-            let result = StringArray::from(strings);
-            EvalResult::StringArray(Box::leak(Box::new(result)))
-        },
-        EvalResult::Int64Array(arr) => {
-            let strings: Vec<String> = (0..arr.len())
-                .map(|i| if arr.is_null(i) { "".to_string() } else { arr.value(i).to_string() })
-                .collect();
-            let result = StringArray::from(strings);
-            EvalResult::StringArray(Box::leak(Box::new(result)))
-        },
-        EvalResult::IntConst(v) => EvalResult::StringConst(v.to_string()),
-        _ => panic!("to_string() not implemented for this type"),
-    }
+    convert_result("to_string", arg, &DataType::Utf8)
 }
 
 /// min(array) - returns minimum value
@@ -618,35 +749,15 @@ fn func_min<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
         panic!("min() requires exactly 1 argument");
     }
     
-    let arg = eval_jmespath(&args[0], rb);
-    
-    match arg {
-        EvalResult::Int32Array(arr) => {
-            use arrow::compute::min;
-            if let Some(min_val) = min(arr) {
-                EvalResult::IntConst(min_val)
-            } else {
-                panic!("Cannot compute min of empty array");
-            }
-        },
-        EvalResult::Int64Array(arr) => {
-            use arrow::compute::min;
-            if let Some(min_val) = min(arr) {
-                EvalResult::Int64Const(min_val)
-            } else {
-                panic!("Cannot compute min of empty array");
-            }
-        },
-        EvalResult::Float64Array(arr) => {
-            use arrow::compute::min;
-            if let Some(min_val) = min(arr) {
-                EvalResult::FloatConst(min_val)
-            } else {
-                panic!("Cannot compute min of empty array");
-            }
-        },
-        _ => panic!("min() requires numeric array"),
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    if let Some(result) = float_aggregate("min", &arg) {
+        return result;
     }
+    
+    numeric_min_max("min", &arg, false)
 }
 
 /// max(array) - returns maximum value
@@ -655,35 +766,60 @@ fn func_max<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
         panic!("max() requires exactly 1 argument");
     }
     
-    let arg = eval_jmespath(&args[0], rb);
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    if let Some(result) = float_aggregate("max", &arg) {
+        return result;
+    }
     
-    match arg {
-        EvalResult::Int32Array(arr) => {
-            use arrow::compute::max;
-            if let Some(max_val) = max(arr) {
-                EvalResult::IntConst(max_val)
-            } else {
-                panic!("Cannot compute max of empty array");
-            }
-        },
-        EvalResult::Int64Array(arr) => {
-            use arrow::compute::max;
-            if let Some(max_val) = max(arr) {
-                EvalResult::Int64Const(max_val)
-            } else {
-                panic!("Cannot compute max of empty array");
-            }
-        },
-        EvalResult::Float64Array(arr) => {
-            use arrow::compute::max;
-            if let Some(max_val) = max(arr) {
-                EvalResult::FloatConst(max_val)
-            } else {
-                panic!("Cannot compute max of empty array");
-            }
-        },
-        _ => panic!("max() requires numeric array"),
+    numeric_min_max("max", &arg, true)
+}
+
+/// min/max/sum/avg of a float column under the NaN policy (None for non-float arguments)
+fn float_aggregate<'a>(func: &str, arg: &EvalResult) -> Option<EvalResult<'a>> {
+    result_to_array(arg).filter(|arr| arr.data_type().is_floating())?;
+    let values = numeric_to_f64(func, arg);
+    if eval_options().float_policy.nan_aggregation == NanAggregation::Propagate
+        && values.iter().flatten().any(f64::is_nan) {
+        return Some(EvalResult::FloatConst(f64::NAN));
     }
+    // Nulls (and skipped NaNs) are neither aggregated nor counted
+    let present: Vec<f64> = values.iter().flatten().filter(|v| !v.is_nan()).collect();
+    // min/max/avg without values are null, an empty sum is 0
+    let result = match func {
+        "sum" => Some(present.iter().sum()),
+        _ if present.is_empty() => None,
+        "min" => present.iter().copied().reduce(f64::min),
+        "max" => present.iter().copied().reduce(f64::max),
+        _ => Some(present.iter().sum::<f64>() / present.len() as f64),
+    };
+    Some(result.map_or(EvalResult::NullConst, EvalResult::FloatConst))
+}
+
+/// min()/max() of an integer or decimal array as a constant (null if empty)
+fn numeric_min_max<'a>(func: &str, arg: &EvalResult, max: bool) -> EvalResult<'a> {
+    use arrow::datatypes::{Decimal128Type, Decimal256Type};
+    let arr = result_to_array(arg)
+        .filter(|arr| arr.data_type().is_numeric())
+        .unwrap_or_else(|| panic!("{}() requires numeric array", func));
+    let result = match arr.data_type() {
+        DataType::Decimal128(_, _) => primitive_min_max::<Decimal128Type>(&arr, max),
+        DataType::Decimal256(_, _) => primitive_min_max::<Decimal256Type>(&arr, max),
+        data_type => numeric_kernel!(func, data_type, primitive_min_max(&arr, max)),
+    };
+    // No non-null values: the result is null
+    result.unwrap_or(EvalResult::NullConst)
+}
+
+/// min()/max() of a primitive numeric array as a constant (None if all values are null)
+fn primitive_min_max<'a, T: arrow::datatypes::ArrowNumericType>(arr: &ArrayRef, max: bool) -> Option<EvalResult<'a>> {
+    use arrow::array::AsArray;
+    let values = arr.as_primitive::<T>();
+    let value = if max { arrow::compute::max(values) } else { arrow::compute::min(values) }?;
+    let value = arrow::array::PrimitiveArray::<T>::from_iter_values([value]).with_data_type(arr.data_type().clone());
+    Some(scalar_from_array(&value, 0))
 }
 
 /// sum(array) - returns sum of all values
@@ -692,111 +828,119 @@ fn func_sum<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
         panic!("sum() requires exactly 1 argument");
     }
     
-    let arg = eval_jmespath(&args[0], rb);
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    // Aggregates ignore nulls, so an all-null argument has no value
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    if let Some(result) = float_aggregate("sum", &arg) {
+        return result;
+    }
     
     match arg {
-        EvalResult::Int32Array(arr) => {
-            use arrow::compute::sum;
-            if let Some(sum_val) = sum(arr) {
-                EvalResult::IntConst(sum_val as i32)
-            } else {
-                EvalResult::IntConst(0)
-            }
-        },
-        EvalResult::Int64Array(arr) => {
-            use arrow::compute::sum;
-            if let Some(sum_val) = sum(arr) {
-                EvalResult::Int64Const(sum_val)
-            } else {
-                EvalResult::Int64Const(0)
-            }
+        EvalResult::DecimalArray(arr) => {
+            use arrow::datatypes::{Decimal128Type, Decimal256Type};
+            let result = match arr.data_type() {
+                // Decimal128 sums that overflow are computed again as Decimal256
+                DataType::Decimal128(_, _) => decimal_sum::<Decimal128Type>(&arr)
+                    .or_else(|| decimal_sum::<Decimal256Type>(&widen_decimal(&arr))),
+                _ => decimal_sum::<Decimal256Type>(&arr),
+            };
+            result.unwrap_or_else(|| panic!("sum() overflowed {:?}", arr.data_type()))
         },
-        EvalResult::Float64Array(arr) => {
-            use arrow::compute::sum;
-            if let Some(sum_val) = sum(arr) {
-                EvalResult::FloatConst(sum_val)
-            } else {
-                EvalResult::FloatConst(0.0)
-            }
+        other => {
+            // Integers sum as Int64 (floats are handled by float_aggregate)
+            let arr = result_to_array(&other)
+                .filter(|arr| arr.data_type().is_integer())
+                .unwrap_or_else(|| panic!("sum() requires numeric array"));
+            let options = arrow::compute::CastOptions { safe: false, ..Default::default() };
+            let total = arrow::compute::cast_with_options(&arr, &DataType::Int64, &options)
+                .and_then(|values| arrow::compute::sum_checked(values.as_any().downcast_ref::<Int64Array>().unwrap()))
+                .unwrap_or_else(|e| panic!("sum() overflowed Int64: {}", e));
+            EvalResult::Int64Const(total.unwrap_or(0))
         },
-        _ => panic!("sum() requires numeric array"),
     }
 }
 
-/// avg(array) - returns average of all values
+/// Exact decimal sum, widened by 10 digits of precision (scale is kept; None on overflow)
+fn decimal_sum<'a, T: arrow::datatypes::DecimalType>(arr: &ArrayRef) -> Option<EvalResult<'a>> {
+    use arrow::array::AsArray;
+    use arrow_array::ArrowNativeTypeOp;
+    let values = arr.as_primitive::<T>();
+    let total = arrow::compute::sum_checked(values).ok()?.unwrap_or(T::Native::ZERO);
+    let (precision, scale) = (values.precision(), values.scale());
+    let result = arrow::array::PrimitiveArray::<T>::from_iter_values([total])
+        .with_precision_and_scale((precision + 10).min(T::MAX_PRECISION), scale)
+        .unwrap();
+    Some(EvalResult::DecimalConst(Arc::new(result)))
+}
+
+/// Exact decimal average with 4 more digits of scale, rounded half away from zero
+/// (null if empty; None on overflow)
+fn decimal_avg<'a, T: arrow::datatypes::DecimalType>(arr: &ArrayRef) -> Option<EvalResult<'a>> {
+    use arrow::array::AsArray;
+    use arrow::datatypes::ArrowNativeType;
+    use arrow_array::ArrowNativeTypeOp;
+    let values = arr.as_primitive::<T>();
+    let count = values.len() - values.null_count();
+    if count == 0 {
+        return Some(EvalResult::NullConst);
+    }
+    let (precision, scale) = (values.precision(), values.scale());
+    let avg_scale = (scale + 4).min(T::MAX_SCALE);
+    let ten = T::Native::usize_as(10);
+    let factor = (scale..avg_scale).try_fold(T::Native::ONE, |f, _| f.mul_checked(ten)).ok()?;
+
+    let total = arrow::compute::sum_checked(values).ok()?.unwrap_or(T::Native::ZERO).mul_checked(factor).ok()?;
+    let (count, half) = (T::Native::usize_as(count), T::Native::usize_as(count / 2));
+    let rounded = if total.is_lt(T::Native::ZERO) { total.sub_checked(half) } else { total.add_checked(half) }.ok()?;
+    let result = arrow::array::PrimitiveArray::<T>::from_iter_values([rounded.div_wrapping(count)])
+        .with_precision_and_scale((precision + 4).min(T::MAX_PRECISION), avg_scale)
+        .unwrap();
+    Some(EvalResult::DecimalConst(Arc::new(result)))
+}
+
+/// Decimal128 column as Decimal256 with the same precision and scale
+fn widen_decimal(arr: &ArrayRef) -> ArrayRef {
+    let DataType::Decimal128(precision, scale) = arr.data_type() else {
+        panic!("Expected a Decimal128 column, got {:?}", arr.data_type());
+    };
+    arrow::compute::cast(arr, &DataType::Decimal256(*precision, *scale)).unwrap()
+}
+
+/// avg(array) - returns average of all non-null values
 fn func_avg<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
     if args.len() != 1 {
         panic!("avg() requires exactly 1 argument");
     }
     
-    let arg = eval_jmespath(&args[0], rb);
-    
-    match arg {
-        EvalResult::Int32Array(arr) => {
-            use arrow::compute::sum;
-            if let Some(sum_val) = sum(arr) {
-                let avg = sum_val as f64 / arr.len() as f64;
-                EvalResult::FloatConst(avg)
-            } else {
-                EvalResult::FloatConst(0.0)
-            }
-        },
-        EvalResult::Int64Array(arr) => {
-            use arrow::compute::sum;
-            if let Some(sum_val) = sum(arr) {
-                let avg = sum_val as f64 / arr.len() as f64;
-                EvalResult::FloatConst(avg)
-            } else {
-                EvalResult::FloatConst(0.0)
-            }
-        },
-        EvalResult::Float64Array(arr) => {
-            use arrow::compute::sum;
-            if let Some(sum_val) = sum(arr) {
-                let avg = sum_val / arr.len() as f64;
-                EvalResult::FloatConst(avg)
-            } else {
-                EvalResult::FloatConst(0.0)
-            }
-        },
-        _ => panic!("avg() requires numeric array"),
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    // Aggregates ignore nulls, so an all-null argument has no value
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
     }
-}
-
-/// abs(value) - returns absolute value
-fn func_abs<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
-    if args.len() != 1 {
-        panic!("abs() requires exactly 1 argument");
+    if let Some(result) = float_aggregate("avg", &arg) {
+        return result;
     }
+    if let EvalResult::DecimalArray(arr) = &arg {
+        use arrow::datatypes::{Decimal128Type, Decimal256Type};
+        let result = match arr.data_type() {
+            DataType::Decimal128(_, _) => decimal_avg::<Decimal128Type>(arr)
+                .or_else(|| decimal_avg::<Decimal256Type>(&widen_decimal(arr))),
+            _ => decimal_avg::<Decimal256Type>(arr),
+        };
+        return result.unwrap_or_else(|| panic!("avg() overflowed {:?}", arr.data_type()));
+    }
+    let values = numeric_to_f64("avg", &arg);
     
-    let arg = eval_jmespath(&args[0], rb);
-    
-    match arg {
-        EvalResult::Int32Array(arr) => {
-            let values: Vec<i32> = (0..arr.len())
-                .map(|i| if arr.is_null(i) { 0 } else { arr.value(i).abs() })
-                .collect();
-            let result = Int32Array::from(values);
-            EvalResult::Int32Array(Box::leak(Box::new(result)))
-        },
-        EvalResult::Int64Array(arr) => {
-            let values: Vec<i64> = (0..arr.len())
-                .map(|i| if arr.is_null(i) { 0 } else { arr.value(i).abs() })
-                .collect();
-            let result = Int64Array::from(values);
-            EvalResult::Int64Array(Box::leak(Box::new(result)))
-        },
-        EvalResult::Float64Array(arr) => {
-            let values: Vec<f64> = (0..arr.len())
-                .map(|i| if arr.is_null(i) { 0.0 } else { arr.value(i).abs() })
-                .collect();
-            let result = Float64Array::from(values);
-            EvalResult::Float64Array(Box::leak(Box::new(result)))
-        },
-        EvalResult::IntConst(v) => EvalResult::IntConst(v.abs()),
-        EvalResult::FloatConst(v) => EvalResult::FloatConst(v.abs()),
-        _ => panic!("abs() requires numeric argument"),
+    // Nulls are neither summed nor counted; without values the average is null
+    let count = values.len() - values.null_count();
+    if count == 0 {
+        return EvalResult::NullConst;
     }
+    
+    use arrow::compute::sum;
+    EvalResult::FloatConst(sum(&values).unwrap_or(0.0) / count as f64)
 }
 
 // ============================================================================
@@ -804,18 +948,20 @@ fn func_abs<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
 // ============================================================================
 
 /// Normalize negative indices: -1 means last element, -2 means second-to-last, etc.
-fn normalize_index(idx: i32, len: usize) -> usize {
-    if idx < 0 {
-        let pos = len as i32 + idx;
-        if pos < 0 {
-            panic!("Index {} out of bounds for length {}", idx, len);
-        }
-        pos as usize
+fn normalize_index(idx: i32, len: usize) -> Option<usize> {
+    let pos = if idx < 0 { len as i64 + idx as i64 } else { idx as i64 };
+    (0..len as i64).contains(&pos).then_some(pos as usize)
+}
+
+/// Resolve slice bounds: Ok((offset, length)) for contiguous slices (step 1),
+/// Err(indices) for stepped or reversed slices
+fn slice_bounds(len: usize, start: Option<i32>, stop: Option<i32>, step: Option<i32>) -> Result<(usize, usize), arrow::array::UInt32Array> {
+    let indices = slice_array(&|i| i as u32, len, start, stop, step);
+    if step.unwrap_or(1) == 1 {
+        let offset = indices.first().map_or(0, |&i| i as usize);
+        Ok((offset, indices.len()))
     } else {
-        if idx as usize >= len {
-            panic!("Index {} out of bounds for length {}", idx, len);
-        }
-        idx as usize
+        Err(arrow::array::UInt32Array::from(indices))
     }
 }
 
@@ -865,29 +1011,244 @@ fn slice_array<T: Clone>(arr: &dyn Fn(usize) -> T, len: usize, start: Option<i32
     result
 }
 
-// ============================================================================
-// OBJECT FUNCTIONS
-// ============================================================================
+/// Convert an array result into a generic ArrayRef (None for constants and RecordBatches)
+fn result_to_array(result: &EvalResult) -> Option<ArrayRef> {
+    match result {
+        EvalResult::Int32Array(arr) => Some(Arc::new(arr.as_ref().clone())),
+        EvalResult::Int64Array(arr) => Some(Arc::new(arr.as_ref().clone())),
+        EvalResult::Float32Array(arr) => Some(Arc::new(arr.as_ref().clone())),
+        EvalResult::Float64Array(arr) => Some(Arc::new(arr.as_ref().clone())),
+        EvalResult::StringArray(arr) => Some(Arc::new(arr.as_ref().clone())),
+        EvalResult::BoolArray(arr) => Some(Arc::new(arr.clone())),
+        EvalResult::TimestampArray(arr) => Some(arr.clone()),
+        EvalResult::BinaryArray(arr) => Some(arr.clone()),
+        EvalResult::NumericArray(arr) => Some(arr.clone()),
+        EvalResult::DecimalArray(arr) => Some(arr.clone()),
+        EvalResult::DictionaryArray(arr) => Some(arr.clone()),
+        EvalResult::MapArray(arr) => Some(arr.clone()),
+        EvalResult::StructArray(arr) => Some(arr.clone()),
+        EvalResult::NullArray(arr) => Some(arr.clone()),
+        EvalResult::ListArray(arr) => Some(Arc::new(arr.as_ref().clone())),
+        EvalResult::LargeListArray(arr) => Some(arr.clone()),
+        _ => None,
+    }
+}
 
-/// keys(object) - returns array of field names from RecordBatch
-fn func_keys<'a>(_args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
-    let schema = rb.schema();
-    let field_names: Vec<String> = schema
-        .fields()
-        .iter()
-        .map(|f| f.name().clone())
-        .collect();
-    
-    let field_name_refs: Vec<&str> = field_names.iter().map(|s| s.as_str()).collect();
-    let keys_array = StringArray::from(field_name_refs);
-    EvalResult::StringArray(Box::leak(Box::new(keys_array)))
+/// Convert a constant result into a single-element array (None for arrays and RecordBatches)
+fn const_to_array(result: &EvalResult) -> Option<ArrayRef> {
+    match result {
+        EvalResult::IntConst(v) => Some(Arc::new(Int32Array::from(vec![*v]))),
+        EvalResult::Int64Const(v) => Some(Arc::new(Int64Array::from(vec![*v]))),
+        EvalResult::FloatConst(v) => Some(Arc::new(Float64Array::from(vec![*v]))),
+        EvalResult::BoolConst(v) => Some(Arc::new(BooleanArray::from(vec![*v]))),
+        EvalResult::StringConst(v) => Some(Arc::new(StringArray::from(vec![v.as_str()]))),
+        EvalResult::TimestampConst(v) => Some(Arc::new(TimestampMicrosecondArray::from(vec![*v]))),
+        EvalResult::StructConst(v) => Some(Arc::new(v.clone())),
+        EvalResult::DecimalConst(v) => Some(v.clone()),
+        EvalResult::NullConst => Some(Arc::new(arrow::array::NullArray::new(1))),
+        _ => None,
+    }
 }
 
-/// values(object) - returns first row values (simplified implementation)
-fn func_values<'a>(_args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
-    // For simplicity, return the number of columns
-    // A full implementation would need to handle multiple rows and types
-    let num_fields = rb.schema().fields().len() as i32;
+/// Wrap a computed array into the matching EvalResult variant
+fn array_to_result<'a>(arr: ArrayRef) -> EvalResult<'a> {
+    match arr.data_type() {
+        DataType::Int32 => EvalResult::Int32Array(Cow::Owned(arr.as_any().downcast_ref::<Int32Array>().unwrap().clone())),
+        DataType::Int64 => EvalResult::Int64Array(Cow::Owned(arr.as_any().downcast_ref::<Int64Array>().unwrap().clone())),
+        DataType::Float32 => EvalResult::Float32Array(Cow::Owned(arr.as_any().downcast_ref::<Float32Array>().unwrap().clone())),
+        DataType::Float64 => EvalResult::Float64Array(Cow::Owned(arr.as_any().downcast_ref::<Float64Array>().unwrap().clone())),
+        DataType::Utf8 => EvalResult::StringArray(Cow::Owned(arr.as_any().downcast_ref::<StringArray>().unwrap().clone())),
+        DataType::Boolean => EvalResult::BoolArray(arr.as_any().downcast_ref::<BooleanArray>().unwrap().clone()),
+        DataType::List(_) => EvalResult::ListArray(Cow::Owned(arr.as_any().downcast_ref::<ListArray>().unwrap().clone())),
+        DataType::LargeList(_) => EvalResult::LargeListArray(arr),
+        // Fixed-size lists and list views are normalized to List/LargeList
+        DataType::FixedSizeList(_, _) | DataType::ListView(_) | DataType::LargeListView(_) => array_to_result(normalize_list(&arr)),
+        DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => EvalResult::TimestampArray(arr),
+        DataType::Binary => EvalResult::BinaryArray(arr),
+        // Large and view string/binary layouts are normalized to Utf8/Binary
+        DataType::LargeUtf8 | DataType::Utf8View => array_to_result(arrow::compute::cast(&arr, &DataType::Utf8).unwrap()),
+        DataType::LargeBinary | DataType::BinaryView => EvalResult::BinaryArray(arrow::compute::cast(&arr, &DataType::Binary).unwrap()),
+        t if is_other_numeric(t) => EvalResult::NumericArray(arr),
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => EvalResult::DecimalArray(arr),
+        DataType::Dictionary(_, _) => EvalResult::DictionaryArray(arr),
+        DataType::Map(_, _) => EvalResult::MapArray(arr),
+        DataType::Struct(_) => EvalResult::StructArray(arr),
+        DataType::Null => EvalResult::NullArray(arr),
+        other => panic!("Unsupported result type: {:?}", other),
+    }
+}
+
+/// Read a single array element as a constant result
+fn scalar_from_array<'a>(arr: &dyn Array, index: usize) -> EvalResult<'a> {
+    if arr.is_null(index) || arr.data_type() == &DataType::Null {
+        return EvalResult::NullConst;
+    }
+    match arr.data_type() {
+        DataType::Int32 => EvalResult::IntConst(arr.as_any().downcast_ref::<Int32Array>().unwrap().value(index)),
+        DataType::Int64 => EvalResult::Int64Const(arr.as_any().downcast_ref::<Int64Array>().unwrap().value(index)),
+        DataType::Float32 => EvalResult::FloatConst(arr.as_any().downcast_ref::<Float32Array>().unwrap().value(index) as f64),
+        DataType::Float64 => EvalResult::FloatConst(arr.as_any().downcast_ref::<Float64Array>().unwrap().value(index)),
+        DataType::Utf8 => EvalResult::StringConst(arr.as_any().downcast_ref::<StringArray>().unwrap().value(index).to_string()),
+        DataType::LargeUtf8 => EvalResult::StringConst(arr.as_string::<i64>().value(index).to_string()),
+        DataType::Utf8View => EvalResult::StringConst(arr.as_string_view().value(index).to_string()),
+        DataType::Boolean => EvalResult::BoolConst(arr.as_any().downcast_ref::<BooleanArray>().unwrap().value(index)),
+        DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => {
+            EvalResult::TimestampConst(temporal_to_micros(arr).value(index))
+        },
+        DataType::UInt64 => {
+            // Values beyond i64 keep their magnitude as float
+            let v = arr.as_any().downcast_ref::<arrow::array::UInt64Array>().unwrap().value(index);
+            i64::try_from(v).map_or(EvalResult::FloatConst(v as f64), EvalResult::Int64Const)
+        },
+        t if t.is_integer() => EvalResult::Int64Const(arrow::compute::cast(&arr.slice(index, 1), &DataType::Int64).unwrap().as_any().downcast_ref::<Int64Array>().unwrap().value(0)),
+        DataType::Float16 => EvalResult::FloatConst(arr.as_any().downcast_ref::<arrow::array::Float16Array>().unwrap().value(index).to_f64()),
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => EvalResult::DecimalConst(arr.slice(index, 1)),
+        DataType::Dictionary(_, _) => {
+            let dict = arr.as_any_dictionary();
+            scalar_from_array(dict.values().as_ref(), dictionary_key(dict, index))
+        },
+        // A list row is the array of its elements
+        DataType::List(_) => array_to_result(arr.as_list::<i32>().value(index)),
+        DataType::LargeList(_) => array_to_result(arr.as_list::<i64>().value(index)),
+        DataType::Struct(_) => EvalResult::StructConst(arr.as_any().downcast_ref::<arrow::array::StructArray>().unwrap().slice(index, 1)),
+        other => panic!("Cannot convert {:?} value to a constant", other),
+    }
+}
+
+/// Cast a numeric array result to Float64 (nulls are preserved)
+fn numeric_to_f64(func: &str, result: &EvalResult) -> Float64Array {
+    let arr = result_to_array(result)
+        .filter(|arr| arr.data_type().is_numeric())
+        .unwrap_or_else(|| panic!("{}() requires numeric array, got: {:?}", func, result));
+    let converted = arrow::compute::cast(&arr, &DataType::Float64)
+        .unwrap_or_else(|e| panic!("{}() failed to convert values: {}", func, e));
+    converted.as_any().downcast_ref::<Float64Array>().unwrap().clone()
+}
+
+/// Numeric column or constant as a comparison operand (None for other types)
+fn numeric_operand(result: &EvalResult) -> Option<Operand> {
+    let operand = match result_to_array(result) {
+        Some(array) => Operand { array, is_scalar: false },
+        None => Operand { array: const_to_array(result)?, is_scalar: true },
+    };
+    operand.array.data_type().is_numeric().then_some(operand)
+}
+
+/// Type in which two numeric operands are compared without losing precision: float constants
+/// take the type of a float column, mixed integers widen to Int64 (Decimal128(20, 0) with UInt64),
+/// anything else with floats to Float64
+fn comparison_type(lhs: &Operand, rhs: &Operand) -> DataType {
+    let (l, r) = (lhs.array.data_type(), rhs.array.data_type());
+    if l == r {
+        return l.clone();
+    }
+    if is_decimal(l) || is_decimal(r) {
+        return decimal_common_type(&[l, r]);
+    }
+    if l.is_floating() && r.is_floating() && lhs.is_scalar != rhs.is_scalar {
+        return if lhs.is_scalar { r.clone() } else { l.clone() };
+    }
+    if l.is_floating() || r.is_floating() {
+        DataType::Float64
+    } else if matches!(l, DataType::UInt64) || matches!(r, DataType::UInt64) {
+        DataType::Decimal128(20, 0)
+    } else {
+        DataType::Int64
+    }
+}
+
+fn is_decimal(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Decimal128(_, _) | DataType::Decimal256(_, _))
+}
+
+/// Decimal type that holds every value of the given numeric types: the largest scale and the most
+/// integer digits (floats count as 18 integer digits with scale 10), Decimal256 if any input is
+fn decimal_common_type(types: &[&DataType]) -> DataType {
+    let (mut int_digits, mut scale, mut wide) = (0i16, 0i16, false);
+    for data_type in types {
+        let (digits, s) = match data_type {
+            DataType::Decimal128(p, s) => (*p as i16 - *s as i16, *s as i16),
+            DataType::Decimal256(p, s) => {
+                wide = true;
+                (*p as i16 - *s as i16, *s as i16)
+            },
+            DataType::Int8 | DataType::UInt8 => (3, 0),
+            DataType::Int16 | DataType::UInt16 => (5, 0),
+            DataType::Int32 | DataType::UInt32 => (10, 0),
+            DataType::Int64 => (19, 0),
+            DataType::UInt64 => (20, 0),
+            _ => (18, 10),
+        };
+        int_digits = int_digits.max(digits);
+        scale = scale.max(s);
+    }
+    if wide || int_digits + scale > 38 {
+        DataType::Decimal256((int_digits + scale).min(76) as u8, scale as i8)
+    } else {
+        DataType::Decimal128((int_digits + scale) as u8, scale as i8)
+    }
+}
+
+/// Apply a comparison operator to two Arrow datums (arrays or scalars of the same type)
+fn compare_datums(op: &CompareOp, lhs: &dyn arrow_array::Datum, rhs: &dyn arrow_array::Datum) -> BooleanArray {
+    // Null policy: == and != never yield null (null == null), ordering with null yields null
+    let result = match op {
+        CompareOp::Eq => cmp::not_distinct(lhs, rhs),
+        CompareOp::Ne => cmp::distinct(lhs, rhs),
+        CompareOp::Gt => cmp::gt(lhs, rhs),
+        CompareOp::Lt => cmp::lt(lhs, rhs),
+        CompareOp::Gte => cmp::gt_eq(lhs, rhs),
+        CompareOp::Lte => cmp::lt_eq(lhs, rhs),
+    };
+    result.unwrap_or_else(|e| panic!("Comparison failed: {}", e))
+}
+
+// ============================================================================
+// OBJECT FUNCTIONS
+// ============================================================================
+
+/// keys(object) - returns array of field names from RecordBatch or struct
+fn func_keys<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let mut schema = rb.schema();
+    if let [arg] = args {
+        match eval_jmespath(arg, rb) {
+            // keys(map_column) - per-row list of the map's keys
+            EvalResult::MapArray(arr) => {
+                let map = arr.as_map();
+                return array_to_result(map_entries_list(map, map.keys()));
+            },
+            // keys(struct_column) - field names of the struct, like `address | keys(@)`
+            EvalResult::StructArray(arr) => schema = Arc::new(arrow::datatypes::Schema::new(arr.as_struct().fields().clone())),
+            EvalResult::StructConst(row) => schema = Arc::new(arrow::datatypes::Schema::new(row.fields().clone())),
+            EvalResult::RecordBatch(batch) => schema = batch.schema(),
+            _ => {},
+        }
+    }
+    
+    let field_names: Vec<String> = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    
+    let field_name_refs: Vec<&str> = field_names.iter().map(|s| s.as_str()).collect();
+    let keys_array = StringArray::from(field_name_refs);
+    EvalResult::StringArray(Cow::Owned(keys_array))
+}
+
+/// values(object) - returns first row values (simplified implementation)
+fn func_values<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    // values(map_column) - per-row list of the map's values
+    if let [arg] = args {
+        if let EvalResult::MapArray(arr) = eval_jmespath(arg, rb) {
+            let map = arr.as_map();
+            return array_to_result(map_entries_list(map, map.values()));
+        }
+    }
+    
+    // For simplicity, return the number of columns
+    // A full implementation would need to handle multiple rows and types
+    let num_fields = rb.schema().fields().len() as i32;
     EvalResult::IntConst(num_fields)
 }
 
@@ -906,13 +1267,32 @@ fn func_type<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
         EvalResult::Float64Array(_) => "array<float64>",
         EvalResult::StringArray(_) => "array<string>",
         EvalResult::BoolArray(_) => "array<boolean>",
-        EvalResult::ListArray(_) => "array<list>",
-        EvalResult::RecordBatch(_) => "object",
+        EvalResult::TimestampArray(_) => "array<timestamp>",
+        EvalResult::BinaryArray(_) => "array<binary>",
+        EvalResult::NumericArray(arr) => match arr.data_type() {
+            DataType::Int8 => "array<int8>",
+            DataType::Int16 => "array<int16>",
+            DataType::UInt8 => "array<uint8>",
+            DataType::UInt16 => "array<uint16>",
+            DataType::UInt32 => "array<uint32>",
+            DataType::UInt64 => "array<uint64>",
+            _ => "array<float16>",
+        },
+        EvalResult::DecimalArray(_) => "array<decimal>",
+        EvalResult::DictionaryArray(_) => "array<dictionary>",
+        EvalResult::MapArray(_) => "array<map>",
+        EvalResult::StructArray(_) => "array<struct>",
+        EvalResult::NullArray(_) => "array<null>",
+        EvalResult::DecimalConst(_) => "number",
+        EvalResult::ListArray(_) | EvalResult::LargeListArray(_) => "array<list>",
+        EvalResult::RecordBatch(_) | EvalResult::StructConst(_) => "object",
         EvalResult::IntConst(_) => "number",
         EvalResult::Int64Const(_) => "number",
         EvalResult::FloatConst(_) => "number",
         EvalResult::BoolConst(_) => "boolean",
         EvalResult::StringConst(_) => "string",
+        EvalResult::TimestampConst(_) => "timestamp",
+        EvalResult::NullConst => "null",
     };
     
     EvalResult::StringConst(type_name.to_string())
@@ -1014,60 +1394,90 @@ fn func_group_by<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'
     
     let group_col = rb_to_group.column(group_col_idx);
     
-    // For now, just return unique values from that column
-    // A full implementation would need aggregation support
-    // We'll use a HashSet to track unique values
-    use std::collections::HashSet;
+    // Unique non-null values in sorted order
+    let (keys, _) = group_keys("group_by", group_col);
+    array_to_result(keys)
+}
+
+/// Distinct non-null keys in sorted order and the group index of every row (None for null keys)
+fn group_keys(func: &str, key: &ArrayRef) -> (ArrayRef, Vec<Option<usize>>) {
+    use arrow::array::UInt32Array;
+    use arrow::compute::{sort_to_indices, take};
+    use std::collections::HashMap;
     
-    match group_col.data_type() {
-        DataType::Int32 => {
-            let arr = group_col.as_any().downcast_ref::<Int32Array>().unwrap();
-            let mut unique_vals = HashSet::new();
-            for i in 0..arr.len() {
-                if !arr.is_null(i) {
-                    unique_vals.insert(arr.value(i));
-                }
-            }
-            let mut sorted_vals: Vec<i32> = unique_vals.into_iter().collect();
-            sorted_vals.sort();
-            let result_arr = Int32Array::from(sorted_vals);
-            EvalResult::Int32Array(Box::leak(Box::new(result_arr)))
-        },
-        DataType::Int64 => {
-            let arr = group_col.as_any().downcast_ref::<Int64Array>().unwrap();
-            let mut unique_vals = HashSet::new();
-            for i in 0..arr.len() {
-                if !arr.is_null(i) {
-                    unique_vals.insert(arr.value(i));
-                }
-            }
-            let mut sorted_vals: Vec<i64> = unique_vals.into_iter().collect();
-            sorted_vals.sort();
-            let result_arr = Int64Array::from(sorted_vals);
-            EvalResult::Int64Array(Box::leak(Box::new(result_arr)))
-        },
-        DataType::Utf8 => {
-            let arr = group_col.as_any().downcast_ref::<StringArray>().unwrap();
-            let mut unique_vals = HashSet::new();
-            for i in 0..arr.len() {
-                if !arr.is_null(i) {
-                    unique_vals.insert(arr.value(i).to_string());
-                }
+    // Dictionary keys: group the dictionary values once, rows map to their value's group
+    let valid = arrow::compute::is_not_null(key.as_ref()).unwrap();
+    if let Some(dict) = key.as_any_dictionary_opt() {
+        let (value_keys, value_groups) = group_keys(func, dict.values());
+        let row_groups: Vec<Option<usize>> = dict.normalized_keys().into_iter().enumerate()
+            .map(|(i, k)| if valid.value(i) { value_groups[k] } else { None })
+            .collect();
+        // Drop dictionary values no row refers to, keeping the sorted order
+        let mut seen = vec![false; value_keys.len()];
+        for group in row_groups.iter().flatten() {
+            seen[*group] = true;
+        }
+        let used: Vec<u32> = (0..seen.len()).filter(|&group| seen[group]).map(|group| group as u32).collect();
+        let mut new_id = vec![0; value_keys.len()];
+        for (id, &group) in used.iter().enumerate() {
+            new_id[group as usize] = id;
+        }
+        let keys = take(value_keys.as_ref(), &UInt32Array::from(used), None).unwrap();
+        return (keys, row_groups.into_iter().map(|g| g.map(|g| new_id[g])).collect());
+    }
+    
+    let encoded = row_encode(func, key);
+    let mut ids = HashMap::new();
+    let mut first_rows: Vec<u32> = Vec::new();
+    let group_of: Vec<Option<usize>> = (0..key.len())
+        .map(|i| {
+            if !valid.value(i) {
+                return None;
             }
-            let mut sorted_vals: Vec<String> = unique_vals.into_iter().collect();
-            sorted_vals.sort();
-            let sorted_refs: Vec<&str> = sorted_vals.iter().map(|s| s.as_str()).collect();
-            let result_arr = StringArray::from(sorted_refs);
-            EvalResult::StringArray(Box::leak(Box::new(result_arr)))
-        },
-        _ => panic!("group_by() not supported for type: {:?}", group_col.data_type()),
+            let id = *ids.entry(encoded.row(i)).or_insert_with(|| {
+                first_rows.push(i as u32);
+                first_rows.len() - 1
+            });
+            Some(id)
+        })
+        .collect();
+    
+    // Sort the distinct keys and renumber the groups accordingly
+    let distinct = take(key.as_ref(), &UInt32Array::from(first_rows), None)
+        .unwrap_or_else(|e| panic!("{}() failed to collect keys: {}", func, e));
+    let order = sort_to_indices(&distinct, None, None)
+        .unwrap_or_else(|e| panic!("{}() not supported for type: {:?}: {}", func, key.data_type(), e));
+    let mut rank = vec![0; order.len()];
+    for (position, &id) in order.values().iter().enumerate() {
+        rank[id as usize] = position;
     }
+    let sorted = take(distinct.as_ref(), &order, None).unwrap();
+    (sorted, group_of.into_iter().map(|g| g.map(|id| rank[id])).collect())
 }
 
 // ============================================================================
-// UTILITY FUNCTIONS (reverse, not_null)
+// UTILITY FUNCTIONS (reverse, sort, not_null)
 // ============================================================================
 
+/// sort(array) - sorts array values ascending (nulls last)
+/// Example: names | sort(@)
+fn func_sort<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::SortOptions;
+    if args.len() != 1 {
+        panic!("sort() requires exactly 1 argument");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    let arr = result_to_array(&arg)
+        .unwrap_or_else(|| panic!("sort() requires an array argument, got: {:?}", arg));
+    let sorted = arrow::compute::sort(&arr, Some(SortOptions { descending: false, nulls_first: false }))
+        .unwrap_or_else(|e| panic!("sort() not supported for type: {:?}: {}", arr.data_type(), e));
+    array_to_result(sorted)
+}
+
 /// reverse(array) - reverses the order of array elements or RecordBatch rows
 /// Example: reverse(sort_by(@, &price)) - descending sort
 fn func_reverse<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
@@ -1076,38 +1486,49 @@ fn func_reverse<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a
     }
     
     let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
     
     match arg {
         EvalResult::Int32Array(arr) => {
             let values: Vec<i32> = (0..arr.len()).rev().map(|i| arr.value(i)).collect();
             let reversed = Int32Array::from(values);
-            EvalResult::Int32Array(Box::leak(Box::new(reversed)))
+            EvalResult::Int32Array(Cow::Owned(reversed))
         },
         EvalResult::Int64Array(arr) => {
             let values: Vec<i64> = (0..arr.len()).rev().map(|i| arr.value(i)).collect();
             let reversed = Int64Array::from(values);
-            EvalResult::Int64Array(Box::leak(Box::new(reversed)))
+            EvalResult::Int64Array(Cow::Owned(reversed))
         },
         EvalResult::Float32Array(arr) => {
             let values: Vec<f32> = (0..arr.len()).rev().map(|i| arr.value(i)).collect();
             let reversed = Float32Array::from(values);
-            EvalResult::Float32Array(Box::leak(Box::new(reversed)))
+            EvalResult::Float32Array(Cow::Owned(reversed))
         },
         EvalResult::Float64Array(arr) => {
             let values: Vec<f64> = (0..arr.len()).rev().map(|i| arr.value(i)).collect();
             let reversed = Float64Array::from(values);
-            EvalResult::Float64Array(Box::leak(Box::new(reversed)))
+            EvalResult::Float64Array(Cow::Owned(reversed))
         },
         EvalResult::StringArray(arr) => {
             let values: Vec<&str> = (0..arr.len()).rev().map(|i| arr.value(i)).collect();
             let reversed = StringArray::from(values);
-            EvalResult::StringArray(Box::leak(Box::new(reversed)))
+            EvalResult::StringArray(Cow::Owned(reversed))
         },
         EvalResult::BoolArray(arr) => {
             let values: Vec<bool> = (0..arr.len()).rev().map(|i| arr.value(i)).collect();
             let reversed = BooleanArray::from(values);
             EvalResult::BoolArray(reversed)
         },
+        EvalResult::TimestampArray(arr) => {
+            use arrow::array::UInt64Array;
+            use arrow::compute::take;
+            let indices = UInt64Array::from((0..arr.len() as u64).rev().collect::<Vec<u64>>());
+            let reversed = take(arr.as_ref(), &indices, None)
+                .unwrap_or_else(|e| panic!("Failed to reverse timestamps: {}", e));
+            EvalResult::TimestampArray(reversed)
+        },
         EvalResult::RecordBatch(batch) => {
             // Reverse all rows in the RecordBatch
             let num_rows = batch.num_rows();
@@ -1147,44 +1568,2871 @@ fn func_not_null<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'
     // Evaluate the argument to get the column
     let arg = eval_jmespath(&args[0], rb);
     
-    // Create boolean mask: true where NOT null
-    let mask = match arg {
-        EvalResult::Int32Array(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+    // Create boolean mask: true where NOT null (logical nulls, so a missing field keeps no rows)
+    let arr = result_to_array(&arg)
+        .unwrap_or_else(|| panic!("not_null() requires an array column, got: {:?}", arg));
+    let mask = arrow::compute::is_not_null(arr.as_ref()).unwrap();
+    
+    // Filter the RecordBatch using the mask
+    let filtered = filter_record_batch(rb, &mask)
+        .unwrap_or_else(|e| panic!("Failed to filter null values: {}", e));
+    
+    EvalResult::RecordBatch(filtered)
+}
+
+// ============================================================================
+// DATE/TIME FUNCTIONS (now, to_timestamp, date_trunc, extract, ts_diff)
+// ============================================================================
+
+/// Normalize a Timestamp/Date32/Date64 array to UTC microseconds (timezone metadata is kept)
+fn temporal_to_micros(arr: &dyn Array) -> TimestampMicrosecondArray {
+    let target = match arr.data_type() {
+        DataType::Timestamp(_, tz) => DataType::Timestamp(TimeUnit::Microsecond, tz.clone()),
+        DataType::Date32 | DataType::Date64 => DataType::Timestamp(TimeUnit::Microsecond, None),
+        other => panic!("Expected timestamp or date array, got {:?}", other),
+    };
+    let converted = arrow::compute::cast(arr, &target)
+        .unwrap_or_else(|e| panic!("Failed to convert {:?} to timestamp: {}", arr.data_type(), e));
+    converted.as_any().downcast_ref::<TimestampMicrosecondArray>().unwrap().clone()
+}
+
+/// Timezone of a temporal data type (None for naive timestamps and dates)
+fn temporal_timezone(data_type: &DataType) -> Option<Arc<str>> {
+    match data_type {
+        DataType::Timestamp(_, tz) => tz.clone(),
+        _ => None,
+    }
+}
+
+/// Build a single-element array of the given temporal type from UTC microseconds
+fn timestamp_scalar(micros: i64, target: &DataType) -> ArrayRef {
+    let arr = TimestampMicrosecondArray::from(vec![micros]).with_timezone_opt(temporal_timezone(target));
+    arrow::compute::cast(&arr, target)
+        .unwrap_or_else(|e| panic!("Failed to convert timestamp to {:?}: {}", target, e))
+}
+
+/// Parse an ISO-8601 string literal into a single-element array of the given temporal type
+fn timestamp_literal(s: &str, target: &DataType) -> ArrayRef {
+    use arrow::compute::{cast_with_options, CastOptions};
+    let options = CastOptions { safe: false, ..Default::default() };
+    cast_with_options(&StringArray::from(vec![s]), target, &options)
+        .unwrap_or_else(|e| panic!("Invalid timestamp literal '{}': {}", s, e))
+}
+
+/// Parse a string into UTC microseconds, either as ISO-8601 or with a chrono format string
+fn try_parse_timestamp(s: &str, format: Option<&str>) -> Option<i64> {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+    match format {
+        None => arrow::compute::kernels::cast_utils::string_to_datetime(&Utc, s)
+            .ok()
+            .map(|dt| dt.timestamp_micros()),
+        Some(fmt) => {
+            if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+                return Some(dt.timestamp_micros());
+            }
+            if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+                return Some(dt.and_utc().timestamp_micros());
+            }
+            NaiveDate::parse_from_str(s, fmt)
+                .ok()
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp_micros())
+        }
+    }
+}
+
+/// Parse a timestamp literal of the query (invalid literals are always an error)
+fn parse_timestamp(s: &str, format: Option<&str>) -> i64 {
+    try_parse_timestamp(s, format)
+        .unwrap_or_else(|| panic!("Cannot parse timestamp '{}'", s))
+}
+
+/// Parse a timestamp value, honoring the configured conversion error mode
+fn convert_timestamp(s: &str, format: Option<&str>) -> Option<i64> {
+    let parsed = try_parse_timestamp(s, format);
+    if parsed.is_none() && eval_options().on_conversion_error == ConversionErrorMode::Error {
+        match format {
+            Some(fmt) => panic!("to_timestamp(): cannot parse '{}' with format '{}'", s, fmt),
+            None => panic!("to_timestamp(): cannot parse '{}'", s),
+        }
+    }
+    parsed
+}
+
+/// Truncate a wall-clock time to the given unit (second, minute, hour, day, week, month, quarter, year)
+fn truncate_naive(dt: chrono::NaiveDateTime, unit: &str) -> Option<chrono::NaiveDateTime> {
+    use chrono::{Datelike, Duration, NaiveDate, Timelike};
+    let date = dt.date();
+    match unit {
+        "second" => dt.with_nanosecond(0),
+        "minute" => dt.with_nanosecond(0)?.with_second(0),
+        "hour" => dt.date().and_hms_opt(dt.hour(), 0, 0),
+        "day" => date.and_hms_opt(0, 0, 0),
+        "week" => (date - Duration::days(date.weekday().num_days_from_monday() as i64)).and_hms_opt(0, 0, 0),
+        "month" => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?.and_hms_opt(0, 0, 0),
+        "quarter" => NaiveDate::from_ymd_opt(date.year(), (date.month() - 1) / 3 * 3 + 1, 1)?.and_hms_opt(0, 0, 0),
+        "year" => NaiveDate::from_ymd_opt(date.year(), 1, 1)?.and_hms_opt(0, 0, 0),
+        _ => panic!("date_trunc(): unsupported unit '{}'", unit),
+    }
+}
+
+/// Truncate UTC microseconds to a unit, interpreting wall-clock time in the given timezone
+fn truncate_micros(micros: i64, unit: &str, tz: Option<&arrow_array::timezone::Tz>) -> Option<i64> {
+    use arrow_array::temporal_conversions::{as_datetime, as_datetime_with_timezone};
+    use arrow_array::types::TimestampMicrosecondType;
+    use chrono::TimeZone;
+    match tz {
+        None => {
+            let naive = as_datetime::<TimestampMicrosecondType>(micros)?;
+            Some(truncate_naive(naive, unit)?.and_utc().timestamp_micros())
         },
-        EvalResult::Int64Array(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+        Some(tz) => {
+            let local = as_datetime_with_timezone::<TimestampMicrosecondType>(micros, *tz)?.naive_local();
+            let truncated = truncate_naive(local, unit)?;
+            Some(tz.from_local_datetime(&truncated).earliest()?.timestamp_micros())
         },
-        EvalResult::Float32Array(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+    }
+}
+
+/// Number of microseconds per unit for ts_diff()
+fn micros_per_unit(unit: &str) -> i64 {
+    match unit {
+        "microsecond" => 1,
+        "millisecond" => 1_000,
+        "second" => 1_000_000,
+        "minute" => 60_000_000,
+        "hour" => 3_600_000_000,
+        "day" => 86_400_000_000,
+        "week" => 604_800_000_000,
+        _ => panic!("ts_diff(): unsupported unit '{}'", unit),
+    }
+}
+
+/// Coerce a function argument to a temporal operand (ISO-8601 strings become TimestampConst)
+fn temporal_operand(arg: EvalResult<'_>) -> EvalResult<'_> {
+    match arg {
+        EvalResult::StringConst(s) => EvalResult::TimestampConst(parse_timestamp(&s, None)),
+        other => other,
+    }
+}
+
+/// Evaluate a string literal argument (unit names, formats)
+fn string_literal_arg(func: &str, arg: &JmesPathExpr, rb: &RecordBatch) -> String {
+    match eval_jmespath(arg, rb) {
+        EvalResult::StringConst(s) => s,
+        other => panic!("{}() expects a string literal argument, got {:?}", func, other),
+    }
+}
+
+/// now() - current UTC time as timestamp constant
+fn func_now<'a>(args: &[JmesPathExpr], _rb: &'a RecordBatch) -> EvalResult<'a> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    if !args.is_empty() {
+        panic!("now() takes no arguments");
+    }
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    EvalResult::TimestampConst(elapsed.as_micros() as i64)
+}
+
+/// to_timestamp(value, format?) - parses strings into timestamps (ISO-8601 or chrono format),
+/// unparsable values follow EvalOptions::on_conversion_error
+/// Example: to_timestamp(created, '%d.%m.%Y %H:%M')
+fn func_to_timestamp<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.is_empty() || args.len() > 2 {
+        panic!("to_timestamp() requires 1 or 2 arguments: to_timestamp(value, format?)");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    let format = args.get(1).map(|f| string_literal_arg("to_timestamp", f, rb));
+    
+    match arg {
+        EvalResult::StringArray(arr) => {
+            let result: TimestampMicrosecondArray = arr.iter()
+                .map(|v| v.and_then(|s| convert_timestamp(s, format.as_deref())))
+                .collect();
+            EvalResult::TimestampArray(Arc::new(result))
         },
-        EvalResult::Float64Array(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+        EvalResult::StringConst(s) => match convert_timestamp(&s, format.as_deref()) {
+            Some(v) => EvalResult::TimestampConst(v),
+            None => EvalResult::NullConst,
         },
-        EvalResult::StringArray(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+        EvalResult::TimestampArray(arr) => EvalResult::TimestampArray(arr),
+        EvalResult::TimestampConst(v) => EvalResult::TimestampConst(v),
+        EvalResult::NullConst => EvalResult::NullConst,
+        EvalResult::NullArray(arr) => EvalResult::NullArray(arr),
+        _ => panic!("to_timestamp() requires string or timestamp argument, got: {:?}", arg),
+    }
+}
+
+/// date_trunc(unit, timestamp) - truncates timestamps to second/minute/hour/day/week/month/quarter/year
+/// Example: date_trunc('hour', ts)
+fn func_date_trunc<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("date_trunc() requires exactly 2 arguments: date_trunc(unit, timestamp)");
+    }
+    
+    let unit = string_literal_arg("date_trunc", &args[0], rb).to_lowercase();
+    let arg = temporal_operand(eval_jmespath(&args[1], rb));
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    
+    match arg {
+        EvalResult::TimestampArray(arr) => {
+            let tz_name = temporal_timezone(arr.data_type());
+            let tz: Option<arrow_array::timezone::Tz> = tz_name.as_ref()
+                .map(|name| name.parse().unwrap_or_else(|e| panic!("Invalid timezone '{}': {}", name, e)));
+            let micros = temporal_to_micros(arr.as_ref());
+            let truncated = micros
+                .unary_opt::<_, arrow_array::types::TimestampMicrosecondType>(|v| truncate_micros(v, &unit, tz.as_ref()))
+                .with_timezone_opt(tz_name);
+            EvalResult::TimestampArray(Arc::new(truncated))
         },
-        EvalResult::BoolArray(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+        EvalResult::TimestampConst(v) => {
+            let truncated = truncate_micros(v, &unit, None)
+                .unwrap_or_else(|| panic!("date_trunc(): timestamp {} out of range", v));
+            EvalResult::TimestampConst(truncated)
+        },
+        _ => panic!("date_trunc() requires timestamp argument, got: {:?}", arg),
+    }
+}
+
+/// extract(part, timestamp) - extracts year/quarter/month/week/day/dow/doy/hour/minute/second/
+/// millisecond/microsecond/nanosecond (Int32) or epoch seconds (Int64)
+/// Example: extract('dow', ts) - day of week, Sunday = 0
+fn func_extract<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::{date_part, DatePart};
+    use arrow_array::types::Int64Type;
+    
+    if args.len() != 2 {
+        panic!("extract() requires exactly 2 arguments: extract(part, timestamp)");
+    }
+    
+    let part_name = string_literal_arg("extract", &args[0], rb).to_lowercase();
+    let arg = temporal_operand(eval_jmespath(&args[1], rb));
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    
+    // Constants are evaluated as single-element UTC arrays
+    let (arr, is_const): (ArrayRef, bool) = match arg {
+        EvalResult::TimestampArray(arr) => (arr, false),
+        EvalResult::TimestampConst(v) => (Arc::new(TimestampMicrosecondArray::from(vec![v])), true),
+        _ => panic!("extract() requires timestamp argument, got: {:?}", arg),
+    };
+    
+    if part_name == "epoch" {
+        let seconds: Int64Array = temporal_to_micros(arr.as_ref()).unary::<_, Int64Type>(|v| v.div_euclid(1_000_000));
+        if is_const {
+            return EvalResult::Int64Const(seconds.value(0));
+        }
+        return EvalResult::Int64Array(Cow::Owned(seconds));
+    }
+    
+    let part = match part_name.as_str() {
+        "year" => DatePart::Year,
+        "quarter" => DatePart::Quarter,
+        "month" => DatePart::Month,
+        "week" => DatePart::Week,
+        "day" => DatePart::Day,
+        "dow" => DatePart::DayOfWeekSunday0,
+        "doy" => DatePart::DayOfYear,
+        "hour" => DatePart::Hour,
+        "minute" => DatePart::Minute,
+        "second" => DatePart::Second,
+        "millisecond" => DatePart::Millisecond,
+        "microsecond" => DatePart::Microsecond,
+        "nanosecond" => DatePart::Nanosecond,
+        _ => panic!("extract(): unsupported part '{}'", part_name),
+    };
+    
+    let values = date_part(arr.as_ref(), part)
+        .unwrap_or_else(|e| panic!("extract() failed: {}", e));
+    let values = values.as_any().downcast_ref::<Int32Array>().unwrap().clone();
+    if is_const {
+        return EvalResult::IntConst(values.value(0));
+    }
+    EvalResult::Int32Array(Cow::Owned(values))
+}
+
+/// ts_diff(end, start, unit?) - difference end - start in the given unit (default: second), truncated
+/// Example: ts_diff(finished, started, 'minute')
+fn func_ts_diff<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow_array::types::Int64Type;
+    
+    if args.len() < 2 || args.len() > 3 {
+        panic!("ts_diff() requires 2 or 3 arguments: ts_diff(end, start, unit?)");
+    }
+    
+    let end = temporal_operand(eval_jmespath(&args[0], rb));
+    let start = temporal_operand(eval_jmespath(&args[1], rb));
+    let unit = args.get(2)
+        .map(|u| string_literal_arg("ts_diff", u, rb).to_lowercase())
+        .unwrap_or_else(|| "second".to_string());
+    let divisor = micros_per_unit(&unit);
+    // Differences beyond the i64 microsecond range are null
+    let diff = |x: i64, y: i64| x.checked_sub(y).map(|d| d / divisor);
+    if let Some(null) = null_propagation(&[&end, &start]) {
+        return null;
+    }
+    
+    let result: Int64Array = match (end, start) {
+        (EvalResult::TimestampArray(a), EvalResult::TimestampArray(b)) => {
+            let (a, b) = (temporal_to_micros(a.as_ref()), temporal_to_micros(b.as_ref()));
+            a.iter().zip(b.iter()).map(|(x, y)| diff(x?, y?)).collect()
+        },
+        (EvalResult::TimestampArray(a), EvalResult::TimestampConst(c)) => {
+            temporal_to_micros(a.as_ref()).unary_opt::<_, Int64Type>(|x| diff(x, c))
         },
-        EvalResult::ListArray(arr) => {
-            let null_mask: Vec<bool> = (0..arr.len()).map(|i| !arr.is_null(i)).collect();
-            BooleanArray::from(null_mask)
+        (EvalResult::TimestampConst(c), EvalResult::TimestampArray(b)) => {
+            temporal_to_micros(b.as_ref()).unary_opt::<_, Int64Type>(|y| diff(c, y))
         },
-        _ => panic!("not_null() requires an array column, got: {:?}", arg),
+        (EvalResult::TimestampConst(x), EvalResult::TimestampConst(y)) => {
+            return diff(x, y).map_or(EvalResult::NullConst, EvalResult::Int64Const);
+        },
+        (end, start) => panic!("ts_diff() requires timestamp arguments, got: {:?}, {:?}", end, start),
     };
     
-    // Filter the RecordBatch using the mask
-    let filtered = filter_record_batch(rb, &mask)
-        .unwrap_or_else(|e| panic!("Failed to filter null values: {}", e));
+    EvalResult::Int64Array(Cow::Owned(result))
+}
+
+// ============================================================================
+// STATISTICAL FUNCTIONS (count, count_distinct, median, percentile, variance, stddev, mode)
+// ============================================================================
+
+/// Non-null values of a numeric array, sorted ascending
+fn sorted_numeric_values(func: &str, result: &EvalResult) -> Vec<f64> {
+    let values = numeric_to_f64(func, result);
+    let mut sorted: Vec<f64> = values.iter().flatten().collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+/// Linear interpolation between closest ranks (same as numpy's default quantile)
+fn interpolated_percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Sample variance (n - 1) of the non-null values (None for fewer than 2 values)
+fn sample_variance(func: &str, result: &EvalResult) -> Option<f64> {
+    let values = numeric_to_f64(func, result);
+    let present: Vec<f64> = values.iter().flatten().collect();
+    if present.len() < 2 {
+        return None;
+    }
+    let mean = present.iter().sum::<f64>() / present.len() as f64;
+    Some(present.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (present.len() - 1) as f64)
+}
+
+/// Row-encode an array so values of any type can be hashed and compared
+fn row_encode(func: &str, arr: &ArrayRef) -> arrow::row::Rows {
+    use arrow::row::{RowConverter, SortField};
+    let converter = RowConverter::new(vec![SortField::new(arr.data_type().clone())])
+        .unwrap_or_else(|e| panic!("{}() not supported for {:?}: {}", func, arr.data_type(), e));
+    converter.convert_columns(std::slice::from_ref(arr))
+        .unwrap_or_else(|e| panic!("{}() failed: {}", func, e))
+}
+
+/// count(array) - number of non-null values, count(@) - number of rows
+fn func_count<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("count() requires exactly 1 argument");
+    }
     
-    EvalResult::RecordBatch(filtered)
+    let arg = eval_jmespath(&args[0], rb);
+    
+    if let EvalResult::RecordBatch(batch) = &arg {
+        return EvalResult::Int64Const(batch.num_rows() as i64);
+    }
+    
+    if let EvalResult::NullConst = arg {
+        return EvalResult::Int64Const(0);
+    }
+    
+    let arr = result_to_array(&arg)
+        .unwrap_or_else(|| panic!("count() requires an array, got: {:?}", arg));
+    EvalResult::Int64Const((arr.len() - arr.logical_null_count()) as i64)
+}
+
+/// count_distinct(array) - number of distinct non-null values
+fn func_count_distinct<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use std::collections::HashSet;
+    
+    if args.len() != 1 {
+        panic!("count_distinct() requires exactly 1 argument");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    if is_null_input(&arg) {
+        return EvalResult::Int64Const(0);
+    }
+    let arr = result_to_array(&arg)
+        .unwrap_or_else(|| panic!("count_distinct() requires an array, got: {:?}", arg));
+    
+    let rows = row_encode("count_distinct", &arr);
+    let valid = arrow::compute::is_not_null(arr.as_ref()).unwrap();
+    let distinct: HashSet<_> = (0..arr.len())
+        .filter(|&i| valid.value(i))
+        .map(|i| rows.row(i))
+        .collect();
+    
+    EvalResult::Int64Const(distinct.len() as i64)
+}
+
+/// median(array) - middle value of the non-null values (mean of the two middle values for even counts, null if empty)
+fn func_median<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("median() requires exactly 1 argument");
+    }
+    
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    let sorted = sorted_numeric_values("median", &arg);
+    if sorted.is_empty() {
+        return EvalResult::NullConst;
+    }
+    
+    EvalResult::FloatConst(interpolated_percentile(&sorted, 0.5))
+}
+
+/// percentile(array, p) - p-th quantile (0 <= p <= 1) with linear interpolation
+/// Example: percentile(latency, 0.95)
+fn func_percentile<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("percentile() requires exactly 2 arguments: percentile(array, p)");
+    }
+    
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    let p = match eval_jmespath(&args[1], rb) {
+        EvalResult::FloatConst(p) => p,
+        EvalResult::IntConst(p) => p as f64,
+        other => panic!("percentile() requires a numeric p, got: {:?}", other),
+    };
+    if !(0.0..=1.0).contains(&p) {
+        panic!("percentile() requires 0 <= p <= 1, got {}", p);
+    }
+    
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    let sorted = sorted_numeric_values("percentile", &arg);
+    if sorted.is_empty() {
+        return EvalResult::NullConst;
+    }
+    
+    EvalResult::FloatConst(interpolated_percentile(&sorted, p))
 }
 
+/// variance(array) - sample variance of the non-null values (null for fewer than 2 values)
+fn func_variance<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("variance() requires exactly 1 argument");
+    }
+    
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    sample_variance("variance", &arg).map_or(EvalResult::NullConst, EvalResult::FloatConst)
+}
 
+/// stddev(array) - sample standard deviation of the non-null values (null for fewer than 2 values)
+fn func_stddev<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("stddev() requires exactly 1 argument");
+    }
+    
+    let arg = aggregate_operand(eval_jmespath(&args[0], rb));
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    sample_variance("stddev", &arg).map_or(EvalResult::NullConst, |v| EvalResult::FloatConst(v.sqrt()))
+}
+
+/// mode(array) - most frequent non-null value (ties: first occurrence wins)
+fn func_mode<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use std::collections::HashMap;
+    
+    if args.len() != 1 {
+        panic!("mode() requires exactly 1 argument");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    if is_null_input(&arg) {
+        return EvalResult::NullConst;
+    }
+    let arr = result_to_array(&arg)
+        .unwrap_or_else(|| panic!("mode() requires an array, got: {:?}", arg));
+    
+    // Count occurrences, remembering where each value appeared first
+    let rows = row_encode("mode", &arr);
+    let valid = arrow::compute::is_not_null(arr.as_ref()).unwrap();
+    let mut counts = HashMap::new();
+    for i in (0..arr.len()).filter(|&i| valid.value(i)) {
+        let entry = counts.entry(rows.row(i)).or_insert((0usize, i));
+        entry.0 += 1;
+    }
+    
+    let (_, first_index) = counts.into_values()
+        .max_by(|(count_a, first_a), (count_b, first_b)| count_a.cmp(count_b).then(first_b.cmp(first_a)))
+        .unwrap_or_else(|| panic!("Cannot compute mode of empty array"));
+    
+    scalar_from_array(arr.as_ref(), first_index)
+}
+
+// ============================================================================
+// CONDITIONAL AND NULL-HANDLING FUNCTIONS (if, case, coalesce, if_null, nullif, is_null)
+// ============================================================================
+
+/// Argument of a vectorized kernel: an array, or a constant stored as a single-element array
+struct Operand {
+    array: ArrayRef,
+    is_scalar: bool,
+}
+
+/// Compare operands of one type (floats under the configured FloatOrdering)
+fn compare_operands(op: &CompareOp, lhs: &Operand, rhs: &Operand) -> BooleanArray {
+    if lhs.array.data_type().is_floating() && eval_options().float_policy.ordering == FloatOrdering::Ieee {
+        ieee_compare(op, lhs, rhs)
+    } else {
+        compare_datums(op, lhs, rhs)
+    }
+}
+
+/// IEEE 754 float comparison (NaN is unordered, -0.0 == 0.0); nulls follow compare_datums
+fn ieee_compare(op: &CompareOp, lhs: &Operand, rhs: &Operand) -> BooleanArray {
+    use arrow::datatypes::Float64Type;
+    let [l, r] = [lhs, rhs].map(|operand| arrow::compute::cast(&operand.array, &DataType::Float64).unwrap());
+    let (l, r) = (l.as_primitive::<Float64Type>(), r.as_primitive::<Float64Type>());
+    let value = |arr: &arrow::array::Float64Array, scalar: bool, i: usize| {
+        let i = if scalar { 0 } else { i };
+        arr.is_valid(i).then(|| arr.value(i))
+    };
+    let len = if lhs.is_scalar { r.len() } else { l.len() };
+    (0..len)
+        .map(|i| match (value(l, lhs.is_scalar, i), value(r, rhs.is_scalar, i)) {
+            (Some(a), Some(b)) => Some(match op {
+                CompareOp::Eq => a == b,
+                CompareOp::Ne => a != b,
+                CompareOp::Gt => a > b,
+                CompareOp::Lt => a < b,
+                CompareOp::Gte => a >= b,
+                CompareOp::Lte => a <= b,
+            }),
+            (a, b) => match op {
+                CompareOp::Eq => Some(a.is_none() && b.is_none()),
+                CompareOp::Ne => Some(a.is_some() || b.is_some()),
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+impl arrow_array::Datum for Operand {
+    fn get(&self) -> (&dyn Array, bool) {
+        (self.array.as_ref(), self.is_scalar)
+    }
+}
+
+/// Turn an evaluated argument into a kernel operand
+fn to_operand(func: &str, result: &EvalResult) -> Operand {
+    if let Some(array) = result_to_array(result) {
+        return Operand { array, is_scalar: false };
+    }
+    if let Some(array) = const_to_array(result) {
+        return Operand { array, is_scalar: true };
+    }
+    panic!("{}() does not support argument: {:?}", func, result);
+}
+
+/// Common type of kernel operands: array types win over constants, mixed numbers widen to Int64/Float64,
+/// null constants adopt the type of the other operands
+fn common_type(func: &str, operands: &[Operand]) -> DataType {
+    let typed: Vec<&Operand> = operands.iter().filter(|o| o.array.data_type() != &DataType::Null).collect();
+    let arrays: Vec<&DataType> = typed.iter().filter(|o| !o.is_scalar).map(|o| o.array.data_type()).collect();
+    let candidates: Vec<&DataType> = if arrays.is_empty() {
+        typed.iter().map(|o| o.array.data_type()).collect()
+    } else {
+        arrays
+    };
+    
+    // Only nulls: keep the null type
+    let Some(first) = candidates.first().copied() else {
+        return DataType::Null;
+    };
+    if candidates.iter().all(|t| *t == first) {
+        first.clone()
+    } else if candidates.iter().all(|t| t.is_numeric()) {
+        if candidates.iter().any(|t| is_decimal(t)) {
+            decimal_common_type(&candidates)
+        } else if candidates.iter().any(|t| t.is_floating()) { DataType::Float64 } else { DataType::Int64 }
+    } else {
+        panic!("{}() arguments have incompatible types: {:?}", func, candidates);
+    }
+}
+
+/// Cast all operands to their common type
+fn unify_operands(func: &str, operands: Vec<Operand>) -> Vec<Operand> {
+    let target = common_type(func, &operands);
+    cast_operands(func, operands, &target)
+}
+
+/// Like unify_operands, but if the array type cannot hold a constant exactly (float bounds of an
+/// integer column, out-of-range integers) all operands widen to the type that holds every value
+fn unify_numeric_operands(func: &str, operands: Vec<Operand>) -> Vec<Operand> {
+    let target = common_type(func, &operands);
+    let fits = |operand: &Operand| {
+        !operand.is_scalar || target.is_floating() || operand.array.data_type() == &DataType::Null
+            || arrow::compute::cast(&operand.array, &target)
+                .and_then(|cast| arrow::compute::cast(&cast, operand.array.data_type()))
+                .is_ok_and(|back| back.to_data() == operand.array.to_data())
+    };
+    if operands.iter().all(fits) {
+        return cast_operands(func, operands, &target);
+    }
+    let as_columns: Vec<Operand> = operands.iter()
+        .map(|operand| Operand { array: operand.array.clone(), is_scalar: false })
+        .collect();
+    let target = common_type(func, &as_columns);
+    cast_operands(func, operands, &target)
+}
+
+/// Cast every operand to `target`
+fn cast_operands(func: &str, operands: Vec<Operand>, target: &DataType) -> Vec<Operand> {
+    operands.into_iter()
+        .map(|operand| {
+            if operand.array.data_type() == target {
+                return operand;
+            }
+            let array = match (operand.array.data_type(), target) {
+                // Timestamp constants carry UTC micros and must keep their instant in the target timezone
+                (DataType::Timestamp(_, None), DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64) if operand.is_scalar => {
+                    let micros = operand.array.as_any().downcast_ref::<TimestampMicrosecondArray>().unwrap().value(0);
+                    timestamp_scalar(micros, target)
+                },
+                _ => arrow::compute::cast(&operand.array, target)
+                    .unwrap_or_else(|e| panic!("{}() cannot convert {:?} to {:?}: {}", func, operand.array.data_type(), target, e)),
+            };
+            Operand { array, is_scalar: operand.is_scalar }
+        })
+        .collect()
+}
+
+/// Number of rows produced by a kernel over these operands (1 if all are constants)
+fn operands_len(operands: &[Operand]) -> usize {
+    operands.iter().filter(|o| !o.is_scalar).map(|o| o.array.len()).max().unwrap_or(1)
+}
+
+/// Repeat a constant operand to the given length
+fn broadcast(operand: &Operand, len: usize) -> ArrayRef {
+    if !operand.is_scalar {
+        return operand.array.clone();
+    }
+    let indices = arrow::array::UInt32Array::from(vec![0u32; len]);
+    arrow::compute::take(operand.array.as_ref(), &indices, None)
+        .unwrap_or_else(|e| panic!("Failed to broadcast constant: {}", e))
+}
+
+/// Wrap a kernel result: constants stay constants, everything else becomes an array result
+fn operand_result<'a>(result: ArrayRef, all_scalar: bool) -> EvalResult<'a> {
+    if all_scalar {
+        return scalar_from_array(result.as_ref(), 0);
+    }
+    array_to_result(result)
+}
+
+/// Evaluate a condition argument as a per-row mask (JMESPath truthiness: null, false and empty values are false)
+fn condition_mask(func: &str, arg: &JmesPathExpr, rb: &RecordBatch, len: usize) -> BooleanArray {
+    match truthy(eval_jmespath(arg, rb)) {
+        EvalResult::BoolArray(mask) => mask,
+        EvalResult::BoolConst(b) => BooleanArray::from(vec![b; len]),
+        other => panic!("{}() condition must be boolean, got: {:?}", func, other),
+    }
+}
+
+/// if(condition, then, else) - per-row choice between two values
+/// Example: if(age >= 18, 'adult', 'minor')
+fn func_if<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::kernels::zip::zip;
+    
+    if args.len() != 3 {
+        panic!("if() requires exactly 3 arguments: if(condition, then, else)");
+    }
+    
+    // A constant condition simply selects one of the branches (conditions follow JMESPath truthiness)
+    let mask = match truthy(eval_jmespath(&args[0], rb)) {
+        EvalResult::BoolConst(b) => return eval_jmespath(if b { &args[1] } else { &args[2] }, rb),
+        EvalResult::BoolArray(mask) => mask,
+        other => panic!("if() condition must be boolean, got: {:?}", other),
+    };
+    
+    let operands = unify_operands("if", vec![
+        to_operand("if", &eval_jmespath(&args[1], rb)),
+        to_operand("if", &eval_jmespath(&args[2], rb)),
+    ]);
+    let result = zip(&mask, &operands[0], &operands[1])
+        .unwrap_or_else(|e| panic!("if() failed: {}", e));
+    
+    array_to_result(result)
+}
+
+/// case(cond1, value1, cond2, value2, ..., default?) - first matching condition wins, null without default
+/// Example: case(score >= 90, 'A', score >= 75, 'B', 'C')
+fn func_case<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::kernels::zip::zip;
+    
+    if args.len() < 2 {
+        panic!("case() requires at least 2 arguments: case(condition, value, ..., default?)");
+    }
+    
+    let pair_count = args.len() / 2;
+    let has_default = args.len() % 2 == 1;
+    
+    let mut values: Vec<Operand> = (0..pair_count)
+        .map(|i| to_operand("case", &eval_jmespath(&args[2 * i + 1], rb)))
+        .collect();
+    if has_default {
+        values.push(to_operand("case", &eval_jmespath(&args[args.len() - 1], rb)));
+    }
+    let values = unify_operands("case", values);
+    let len = operands_len(&values).max(rb.num_rows());
+    
+    // Start from the default (or nulls) and let earlier conditions override later ones
+    let mut result: ArrayRef = if has_default {
+        broadcast(&values[pair_count], len)
+    } else {
+        arrow::array::new_null_array(values[0].array.data_type(), len)
+    };
+    for i in (0..pair_count).rev() {
+        let mask = condition_mask("case", &args[2 * i], rb, len);
+        result = zip(&mask, &values[i], &result)
+            .unwrap_or_else(|e| panic!("case() failed: {}", e));
+    }
+    
+    array_to_result(result)
+}
+
+/// coalesce(a, b, ...) - first non-null value per row
+/// Example: coalesce(nickname, first_name, 'unknown')
+fn func_coalesce<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    coalesce_operands("coalesce", args, rb)
+}
+
+/// if_null(value, fallback) - replaces null values with a fallback
+fn func_if_null<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("if_null() requires exactly 2 arguments: if_null(value, fallback)");
+    }
+    coalesce_operands("if_null", args, rb)
+}
+
+/// Shared implementation of coalesce() and if_null()
+fn coalesce_operands<'a>(func: &str, args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::is_not_null;
+    use arrow::compute::kernels::zip::zip;
+    
+    if args.is_empty() {
+        panic!("{}() requires at least 1 argument", func);
+    }
+    
+    let operands: Vec<Operand> = args.iter()
+        .map(|arg| to_operand(func, &eval_jmespath(arg, rb)))
+        .collect();
+    let operands = unify_operands(func, operands);
+    let all_scalar = operands.iter().all(|o| o.is_scalar);
+    let len = operands_len(&operands);
+    
+    // Fold from the last argument: each earlier argument wins where it is not null
+    let (last, rest) = operands.split_last().unwrap();
+    let mut result = broadcast(last, len);
+    for operand in rest.iter().rev() {
+        let mask = if operand.is_scalar {
+            BooleanArray::from(vec![operand.array.is_valid(0); len])
+        } else {
+            is_not_null(operand.array.as_ref()).unwrap()
+        };
+        result = zip(&mask, operand, &result)
+            .unwrap_or_else(|e| panic!("{}() failed: {}", func, e));
+    }
+    
+    operand_result(result, all_scalar)
+}
+
+/// nullif(value, other) - null where value equals other, value otherwise
+/// Example: nullif(status, 'unknown')
+fn func_nullif<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::nullif;
+    
+    if args.len() != 2 {
+        panic!("nullif() requires exactly 2 arguments: nullif(value, other)");
+    }
+    
+    let operands = unify_operands("nullif", vec![
+        to_operand("nullif", &eval_jmespath(&args[0], rb)),
+        to_operand("nullif", &eval_jmespath(&args[1], rb)),
+    ]);
+    let all_scalar = operands.iter().all(|o| o.is_scalar);
+    let len = operands_len(&operands);
+    
+    let equal = compare_datums(&CompareOp::Eq, &operands[0], &operands[1]);
+    let result = nullif(broadcast(&operands[0], len).as_ref(), &equal)
+        .unwrap_or_else(|e| panic!("nullif() failed: {}", e));
+    
+    operand_result(result, all_scalar)
+}
+
+/// is_null(value) - per-row null check (`null` is null, other constants never are)
+fn func_is_null<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("is_null() requires exactly 1 argument");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(arr) = result_to_array(&arg) {
+        return EvalResult::BoolArray(arrow::compute::is_null(arr.as_ref()).unwrap());
+    }
+    if let EvalResult::NullConst = arg {
+        return EvalResult::BoolConst(true);
+    }
+    if const_to_array(&arg).is_some() {
+        return EvalResult::BoolConst(false);
+    }
+    panic!("is_null() requires an array or constant, got: {:?}", arg);
+}
+
+// ============================================================================
+// CONVERSION FUNCTIONS (to_number, to_boolean, cast; to_string and to_timestamp above)
+// ============================================================================
+
+/// Cast an array, honoring the configured conversion error mode (nulls stay null)
+fn convert_array(func: &str, arr: &ArrayRef, target: &DataType) -> ArrayRef {
+    use arrow::compute::{cast_with_options, CastOptions};
+    let safe = eval_options().on_conversion_error == ConversionErrorMode::Null;
+    let options = CastOptions { safe, ..Default::default() };
+    cast_with_options(arr, target, &options)
+        .unwrap_or_else(|e| panic!("{}() cannot convert {:?} to {:?}: {}", func, arr.data_type(), target, e))
+}
+
+/// Convert an array or constant result to the target type
+fn convert_result<'a>(func: &str, arg: EvalResult<'a>, target: &DataType) -> EvalResult<'a> {
+    if let Some(arr) = result_to_array(&arg) {
+        if arr.data_type() == target {
+            return arg;
+        }
+        return array_to_result(convert_array(func, &arr, target));
+    }
+    if let Some(arr) = const_to_array(&arg) {
+        return scalar_from_array(convert_array(func, &arr, target).as_ref(), 0);
+    }
+    panic!("{}() not supported for: {:?}", func, arg);
+}
+
+/// Map a type name of cast() to an Arrow data type
+fn cast_target_type(name: &str) -> DataType {
+    match name {
+        "int32" | "int" => DataType::Int32,
+        "int64" | "long" => DataType::Int64,
+        "float32" | "float" => DataType::Float32,
+        "float64" | "double" | "number" => DataType::Float64,
+        "string" | "utf8" => DataType::Utf8,
+        "boolean" | "bool" => DataType::Boolean,
+        "timestamp" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "date" | "date32" => DataType::Date32,
+        "date64" => DataType::Date64,
+        _ => panic!("cast(): unsupported target type '{}'", name),
+    }
+}
+
+/// to_number(value) - numbers stay unchanged, booleans become null (JMESPath semantics),
+/// everything else is converted to float64
+fn func_to_number<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("to_number() requires exactly 1 argument");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    match &arg {
+        EvalResult::IntConst(_) | EvalResult::Int64Const(_) | EvalResult::FloatConst(_) => arg,
+        EvalResult::BoolConst(_) => EvalResult::NullConst,
+        EvalResult::BoolArray(arr) => EvalResult::NullArray(Arc::new(arrow::array::NullArray::new(arr.len()))),
+        _ if result_to_array(&arg).is_some_and(|arr| arr.data_type().is_numeric()) => arg,
+        _ => convert_result("to_number", arg, &DataType::Float64),
+    }
+}
+
+/// to_boolean(value) - converts strings ('true', 'yes', '1', ...) and numbers (non-zero) to booleans
+fn func_to_boolean<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("to_boolean() requires exactly 1 argument");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    convert_result("to_boolean", arg, &DataType::Boolean)
+}
+
+/// cast(value, 'type') - general conversion via arrow::compute::cast
+/// Types: int32, int64, float32, float64, string, boolean, timestamp, date32, date64
+/// Example: cast(price, 'int64')
+fn func_cast<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("cast() requires exactly 2 arguments: cast(value, 'type')");
+    }
+    
+    let arg = eval_jmespath(&args[0], rb);
+    let target = cast_target_type(&string_literal_arg("cast", &args[1], rb).to_lowercase());
+    convert_result("cast", arg, &target)
+}
+
+// ============================================================================
+// JSON FUNCTIONS (parse_json, to_json, json_extract)
+// ============================================================================
+
+/// Parse one JSON document; invalid input becomes null or aborts (see ConversionErrorMode)
+fn parse_json_value(func: &str, text: &str) -> serde_json::Value {
+    parse_json_text(func, text).unwrap_or(serde_json::Value::Null)
+}
+
+/// Deserialize one JSON document (None for invalid input unless ConversionErrorMode::Error)
+fn parse_json_text<'t, T: serde::Deserialize<'t>>(func: &str, text: &'t str) -> Option<T> {
+    match serde_json::from_str(text) {
+        Ok(value) => Some(value),
+        Err(e) => match eval_options().on_conversion_error {
+            ConversionErrorMode::Null => None,
+            ConversionErrorMode::Error => panic!("{}() cannot parse '{}' as JSON: {}", func, text, e),
+        },
+    }
+}
+
+/// Value at `path` in one JSON document: only the objects and arrays along the path are split
+/// into raw members, everything else is skipped without building a DOM (None if missing)
+fn json_lookup(func: &str, text: &str, path: &[String]) -> Option<serde_json::Value> {
+    use serde_json::value::RawValue;
+    use std::collections::HashMap;
+    let mut current: &RawValue = parse_json_text(func, text)?;
+    for segment in path {
+        let raw = current.get();
+        current = if raw.starts_with('{') {
+            let mut members: HashMap<String, &RawValue> = serde_json::from_str(raw).ok()?;
+            members.remove(segment)?
+        } else if raw.starts_with('[') {
+            let items: Vec<&RawValue> = serde_json::from_str(raw).ok()?;
+            *items.get(segment.parse::<usize>().ok()?)?
+        } else {
+            return None;
+        };
+    }
+    serde_json::from_str(current.get()).ok()
+}
+
+/// Build a typed Arrow array from JSON values (same schema inference as json_to_arrow)
+fn json_values_to_array(values: Vec<serde_json::Value>) -> ArrayRef {
+    if values.is_empty() {
+        return Arc::new(arrow::array::NullArray::new(0));
+    }
+    // Wrap every value into a one-field object so scalars, lists and objects share one path
+    let rows = values
+        .into_iter()
+        .map(|value| serde_json::json!({ "value": value }))
+        .collect();
+    json_to_arrow(&serde_json::Value::Array(rows)).column(0).clone()
+}
+
+/// Wrap a parsed JSON column: objects become a RecordBatch, everything else an array result
+fn json_array_to_result<'a>(arr: ArrayRef) -> EvalResult<'a> {
+    match arr.data_type() {
+        DataType::Struct(_) => {
+            let struct_arr = arr.as_any().downcast_ref::<StructArray>().unwrap();
+            EvalResult::RecordBatch(struct_to_batch(struct_arr))
+        },
+        // Only nulls (or unparseable input) stay a null column of the input length
+        _ => array_to_result(arr),
+    }
+}
+
+/// Convert a struct column into a RecordBatch; null rows become null in every child column
+fn struct_to_batch(arr: &StructArray) -> RecordBatch {
+    use arrow::buffer::NullBuffer;
+    use arrow::datatypes::Schema;
+
+    let (fields, columns, nulls) = arr.clone().into_parts();
+    let columns = columns
+        .into_iter()
+        .map(|column| match &nulls {
+            Some(row_nulls) if column.data_type() != &DataType::Null => {
+                let merged = NullBuffer::union(Some(row_nulls), column.nulls());
+                make_array(column.into_data().into_builder().nulls(merged).build().unwrap())
+            },
+            _ => column,
+        })
+        .collect();
+    let fields: Vec<_> = fields.iter().map(|f| f.as_ref().clone().with_nullable(true)).collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+/// Split 'a.b[0]' into the path segments ["a", "b", "0"]
+fn json_path_segments(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (name, indices) = match segment.find('[') {
+            Some(pos) => segment.split_at(pos),
+            None => (segment, ""),
+        };
+        if !name.is_empty() {
+            segments.push(name.to_string());
+        }
+        for index in indices.split('[').filter(|s| !s.is_empty()) {
+            let index = index.strip_suffix(']')
+                .filter(|i| i.parse::<usize>().is_ok())
+                .unwrap_or_else(|| panic!("json_extract() invalid path segment: '{}'", segment));
+            segments.push(index.to_string());
+        }
+    }
+    segments
+}
+
+/// Evaluate a JSON string argument (column or constant) into optional strings per row
+fn json_text_arg(func: &str, arg: &EvalResult) -> Vec<Option<String>> {
+    match arg {
+        EvalResult::StringArray(arr) => arr.iter().map(|v| v.map(|s| s.to_string())).collect(),
+        EvalResult::StringConst(s) => vec![Some(s.clone())],
+        _ => panic!("{}() requires a string argument, got: {:?}", func, arg),
+    }
+}
+
+/// parse_json(string) - parses JSON strings into a typed struct/list column
+/// Example: parse_json(payload) | user.id
+fn func_parse_json<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("parse_json() requires exactly 1 argument");
+    }
+
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    let values = json_text_arg("parse_json", &arg)
+        .into_iter()
+        .map(|text| text.map_or(serde_json::Value::Null, |t| parse_json_value("parse_json", &t)))
+        .collect();
+
+    let result = json_values_to_array(values);
+    match arg {
+        // A single document stays a constant unless it is an object or list
+        EvalResult::StringConst(_) if !matches!(result.data_type(), DataType::Struct(_) | DataType::List(_)) => {
+            scalar_from_array(result.as_ref(), 0)
+        },
+        _ => json_array_to_result(result),
+    }
+}
+
+/// to_json(value) - serializes each row (struct, list or scalar) into a JSON string
+fn func_to_json<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("to_json() requires exactly 1 argument");
+    }
+
+    let arg = eval_jmespath(&args[0], rb);
+    let (arr, is_scalar): (ArrayRef, bool) = match &arg {
+        EvalResult::RecordBatch(batch) => (Arc::new(StructArray::from(batch.clone())), false),
+        EvalResult::NullConst => return EvalResult::NullConst,
+        other => match result_to_array(other) {
+            Some(arr) => (arr, false),
+            None => (const_to_array(other).unwrap(), true),
+        },
+    };
+
+    let valid = arrow::compute::is_not_null(arr.as_ref()).unwrap();
+    let strings: StringArray = (0..arr.len())
+        .map(|i| {
+            if !valid.value(i) {
+                None
+            } else {
+                Some(array_value_to_json(arr.as_ref(), i).to_string())
+            }
+        })
+        .collect();
+
+    if is_scalar {
+        return scalar_from_array(&strings, 0);
+    }
+    EvalResult::StringArray(Cow::Owned(strings))
+}
+
+/// json_extract(string, 'a.b[0]') - extracts a sub-value from each JSON string
+/// Only the extracted values are materialized; missing paths yield null.
+fn func_json_extract<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("json_extract() requires exactly 2 arguments");
+    }
+
+    let arg = eval_jmespath(&args[0], rb);
+    let path = json_path_segments(&string_literal_arg("json_extract", &args[1], rb));
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+
+    let values = json_text_arg("json_extract", &arg)
+        .into_iter()
+        .map(|text| {
+            text.and_then(|t| json_lookup("json_extract", &t, &path))
+                .unwrap_or(serde_json::Value::Null)
+        })
+        .collect();
+
+    let result = json_values_to_array(values);
+    match arg {
+        EvalResult::StringConst(_) if !matches!(result.data_type(), DataType::Struct(_) | DataType::List(_)) => {
+            scalar_from_array(result.as_ref(), 0)
+        },
+        _ => json_array_to_result(result),
+    }
+}
+
+// ============================================================================
+// HASHING & ENCODING FUNCTIONS
+// ============================================================================
+
+/// Apply `f` to the bytes of every row: UTF-8 bytes for strings, raw bytes for binary,
+/// little-endian i64 bytes for integers (so Int32 and Int64 columns hash alike). Nulls stay null.
+fn map_bytes<T>(func: &str, arg: &EvalResult, f: impl Fn(&[u8]) -> T) -> Vec<Option<T>> {
+    let arr = result_to_array(arg)
+        .or_else(|| const_to_array(arg))
+        .unwrap_or_else(|| panic!("{}() requires a string, binary or integer argument, got: {:?}", func, arg));
+
+    match arr.data_type() {
+        DataType::Utf8 => {
+            let strings = arr.as_any().downcast_ref::<StringArray>().unwrap();
+            strings.iter().map(|v| v.map(|s| f(s.as_bytes()))).collect()
+        },
+        DataType::Binary => {
+            let binary = arr.as_any().downcast_ref::<arrow::array::BinaryArray>().unwrap();
+            binary.iter().map(|v| v.map(&f)).collect()
+        },
+        DataType::Null => (0..arr.len()).map(|_| None).collect(),
+        data_type if data_type.is_integer() => {
+            let ints = arrow::compute::cast(&arr, &DataType::Int64).unwrap();
+            let ints = ints.as_any().downcast_ref::<Int64Array>().unwrap();
+            ints.iter().map(|v| v.map(|i| f(&i.to_le_bytes()))).collect()
+        },
+        other => panic!("{}() requires a string, binary or integer argument, got: {:?}", func, other),
+    }
+}
+
+/// Wrap a computed array: constants in, constant out (binary results stay arrays)
+fn bytes_result<'a>(arg: &EvalResult, result: ArrayRef) -> EvalResult<'a> {
+    let is_const = const_to_array(arg).is_some();
+    if is_const && result.data_type() != &DataType::Binary {
+        return scalar_from_array(result.as_ref(), 0);
+    }
+    array_to_result(result)
+}
+
+/// Evaluate the single argument of a hashing/encoding function
+fn single_arg<'a>(func: &str, args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("{}() requires exactly 1 argument", func);
+    }
+    eval_jmespath(&args[0], rb)
+}
+
+/// xxHash64 (seed 0) reinterpreted as a signed 64-bit integer
+fn xxhash64_value(bytes: &[u8]) -> i64 {
+    xxhash_rust::xxh64::xxh64(bytes, 0) as i64
+}
+
+/// md5(value) - MD5 digest as lowercase hex string
+fn func_md5<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use md5::{Digest, Md5};
+    let arg = single_arg("md5", args, rb);
+    let digests = StringArray::from(map_bytes("md5", &arg, |b| hex::encode(Md5::digest(b))));
+    bytes_result(&arg, Arc::new(digests))
+}
+
+/// sha256(value) - SHA-256 digest as lowercase hex string
+fn func_sha256<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use sha2::{Digest, Sha256};
+    let arg = single_arg("sha256", args, rb);
+    let digests = StringArray::from(map_bytes("sha256", &arg, |b| hex::encode(Sha256::digest(b))));
+    bytes_result(&arg, Arc::new(digests))
+}
+
+/// xxhash64(value) - fast non-cryptographic 64-bit hash (Int64)
+fn func_xxhash64<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let arg = single_arg("xxhash64", args, rb);
+    let hashes = Int64Array::from(map_bytes("xxhash64", &arg, xxhash64_value));
+    bytes_result(&arg, Arc::new(hashes))
+}
+
+/// hash_bucket(value, n) - stable bucket in [0, n) based on xxhash64
+/// Example: hash_bucket(user_id, 16)
+fn func_hash_bucket<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("hash_bucket() requires exactly 2 arguments");
+    }
+
+    let arg = eval_jmespath(&args[0], rb);
+    let buckets = match eval_jmespath(&args[1], rb) {
+        EvalResult::IntConst(n) if n > 0 => n as u64,
+        EvalResult::Int64Const(n) if n > 0 && n <= i32::MAX as i64 => n as u64,
+        other => panic!("hash_bucket() requires a positive bucket count, got: {:?}", other),
+    };
+
+    let bucket_ids = Int32Array::from(map_bytes("hash_bucket", &arg, |b| {
+        (xxhash_rust::xxh64::xxh64(b, 0) % buckets) as i32
+    }));
+    bytes_result(&arg, Arc::new(bucket_ids))
+}
+
+/// base64_encode(value) - standard Base64 (with padding) as string
+fn func_base64_encode<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use base64::Engine;
+    let arg = single_arg("base64_encode", args, rb);
+    let encoded = StringArray::from(map_bytes("base64_encode", &arg, |b| {
+        base64::engine::general_purpose::STANDARD.encode(b)
+    }));
+    bytes_result(&arg, Arc::new(encoded))
+}
+
+/// base64_decode(string) - decodes Base64 into a binary column
+/// Invalid input becomes null (or aborts with ConversionErrorMode::Error).
+/// Use to_string(base64_decode(x)) to get text back.
+fn func_base64_decode<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use base64::Engine;
+    let arg = single_arg("base64_decode", args, rb);
+    let mode = eval_options().on_conversion_error;
+    let decoded: Vec<Option<Vec<u8>>> = map_bytes("base64_decode", &arg, |b| {
+        match base64::engine::general_purpose::STANDARD.decode(b) {
+            Ok(bytes) => Some(bytes),
+            Err(e) => match mode {
+                ConversionErrorMode::Null => None,
+                ConversionErrorMode::Error => {
+                    panic!("base64_decode() cannot decode '{}': {}", String::from_utf8_lossy(b), e)
+                },
+            },
+        }
+    })
+    .into_iter()
+    .map(Option::flatten)
+    .collect();
+
+    let result = arrow::array::BinaryArray::from_iter(decoded);
+    bytes_result(&arg, Arc::new(result))
+}
+
+/// hex(value) - lowercase hex of the bytes of a string/binary value;
+/// integers are rendered as hexadecimal numbers (hex(255) == 'ff')
+fn func_hex<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let arg = single_arg("hex", args, rb);
+    let is_integer = result_to_array(&arg)
+        .or_else(|| const_to_array(&arg))
+        .is_some_and(|arr| arr.data_type().is_integer());
+
+    let encoded = if is_integer {
+        StringArray::from(map_bytes("hex", &arg, |b| {
+            format!("{:x}", i64::from_le_bytes(b.try_into().unwrap()))
+        }))
+    } else {
+        StringArray::from(map_bytes("hex", &arg, |b| hex::encode(b)))
+    };
+    bytes_result(&arg, Arc::new(encoded))
+}
+
+// ============================================================================
+// LIST FUNCTIONS (per row on ListArray/LargeListArray)
+// ============================================================================
+
+/// A list column viewed as per-row ranges into its flat child values
+struct ListRows {
+    rows: Vec<Option<std::ops::Range<usize>>>,
+    values: ArrayRef,
+    field: arrow::datatypes::FieldRef,
+    large: bool,
+}
+
+/// Row ranges of a List/LargeList array (None for null rows)
+fn generic_list_ranges<O: arrow::array::OffsetSizeTrait>(list: &arrow::array::GenericListArray<O>) -> Vec<Option<std::ops::Range<usize>>> {
+    list.value_offsets()
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| list.is_valid(i).then(|| w[0].as_usize()..w[1].as_usize()))
+        .collect()
+}
+
+/// All non-null elements of a list column, in row order
+fn list_elements(func: &str, result: &EvalResult) -> ArrayRef {
+    let list = list_rows(func, result);
+    let valid = arrow::compute::is_not_null(list.values.as_ref()).unwrap();
+    let indices = list.rows.iter()
+        .flatten()
+        .flat_map(|range| range.clone())
+        .filter(|&i| valid.value(i));
+    let indices = arrow::array::UInt64Array::from_iter_values(indices.map(|i| i as u64));
+    arrow::compute::take(list.values.as_ref(), &indices, None)
+        .unwrap_or_else(|e| panic!("Failed to flatten list: {}", e))
+}
+
+/// Numeric aggregates over a list column run over all of its elements (`orders[*].total | sum(@)`)
+fn aggregate_operand(arg: EvalResult<'_>) -> EvalResult<'_> {
+    match arg {
+        EvalResult::ListArray(_) | EvalResult::LargeListArray(_) => array_to_result(list_elements("aggregate", &arg)),
+        other => other,
+    }
+}
+
+/// Read a list argument (List or LargeList column)
+fn list_rows(func: &str, result: &EvalResult) -> ListRows {
+    let arr = result_to_array(result)
+        .unwrap_or_else(|| panic!("{}() requires a list argument, got: {:?}", func, result));
+    list_rows_from_array(func, &arr)
+}
+
+/// View a list array (any layout) as row ranges
+fn list_rows_from_array(func: &str, arr: &ArrayRef) -> ListRows {
+    use arrow::array::AsArray;
+    let arr = &normalize_list(arr);
+    match arr.data_type() {
+        DataType::List(field) => {
+            let list = arr.as_list::<i32>();
+            ListRows { rows: generic_list_ranges(list), values: list.values().clone(), field: field.clone(), large: false }
+        },
+        DataType::LargeList(field) => {
+            let list = arr.as_list::<i64>();
+            ListRows { rows: generic_list_ranges(list), values: list.values().clone(), field: field.clone(), large: true }
+        },
+        other => panic!("{}() requires a list argument, got: {:?}", func, other),
+    }
+}
+
+/// Convert FixedSizeList to List and ListView/LargeListView to List/LargeList
+/// (fixed-size lists share their values, list views are rewritten in offset order)
+fn normalize_list(arr: &ArrayRef) -> ArrayRef {
+    use arrow::array::{GenericListViewArray, OffsetSizeTrait};
+
+    fn rows_of_view<O: OffsetSizeTrait>(view: &GenericListViewArray<O>) -> Vec<Option<Vec<usize>>> {
+        (0..view.len())
+            .map(|i| view.is_valid(i).then(|| {
+                let offset = view.value_offsets()[i].as_usize();
+                (offset..offset + view.value_sizes()[i].as_usize()).collect()
+            }))
+            .collect()
+    }
+
+    match arr.data_type() {
+        DataType::FixedSizeList(field, _) => arrow::compute::cast(arr, &DataType::List(field.clone())).unwrap(),
+        DataType::ListView(field) => {
+            let view = arr.as_any().downcast_ref::<GenericListViewArray<i32>>().unwrap();
+            let list = ListRows { rows: vec![], values: view.values().clone(), field: field.clone(), large: false };
+            build_list(&list, view.values(), rows_of_view(view))
+        },
+        DataType::LargeListView(field) => {
+            let view = arr.as_any().downcast_ref::<GenericListViewArray<i64>>().unwrap();
+            let list = ListRows { rows: vec![], values: view.values().clone(), field: field.clone(), large: true };
+            build_list(&list, view.values(), rows_of_view(view))
+        },
+        _ => arr.clone(),
+    }
+}
+
+/// Build a list array of the same list type from per-row indices into `values`
+fn build_list(list: &ListRows, values: &ArrayRef, rows: Vec<Option<Vec<usize>>>) -> ArrayRef {
+    use arrow::array::{GenericListArray, UInt64Array};
+    use arrow::buffer::{NullBuffer, OffsetBuffer};
+
+    let nulls = NullBuffer::from(rows.iter().map(|r| r.is_some()).collect::<Vec<bool>>());
+    let lengths: Vec<usize> = rows.iter().map(|r| r.as_ref().map_or(0, |r| r.len())).collect();
+    let indices = UInt64Array::from_iter_values(rows.into_iter().flatten().flatten().map(|i| i as u64));
+    let taken = arrow::compute::take(values.as_ref(), &indices, None)
+        .unwrap_or_else(|e| panic!("Failed to build list: {}", e));
+    let field = Arc::new(list.field.as_ref().clone().with_data_type(values.data_type().clone()));
+
+    if list.large {
+        Arc::new(GenericListArray::<i64>::new(field, OffsetBuffer::from_lengths(lengths), taken, Some(nulls)))
+    } else {
+        Arc::new(GenericListArray::<i32>::new(field, OffsetBuffer::from_lengths(lengths), taken, Some(nulls)))
+    }
+}
+
+/// Evaluate a constant integer argument
+fn int_arg(func: &str, arg: &JmesPathExpr, rb: &RecordBatch) -> i32 {
+    match eval_jmespath(arg, rb) {
+        EvalResult::IntConst(v) => v,
+        other => panic!("{}() expects an integer literal argument, got {:?}", func, other),
+    }
+}
+
+/// Encode list elements and the searched value(s) with one row converter so they can be compared.
+/// The needle is either a constant or a column with one value per row; values that cannot be
+/// represented in the element type never match.
+fn element_rows(func: &str, list: &ListRows, needle: &EvalResult) -> (arrow::row::Rows, arrow::row::Rows, Vec<bool>) {
+    use arrow::row::{RowConverter, SortField};
+    let needle = result_to_array(needle)
+        .or_else(|| const_to_array(needle))
+        .unwrap_or_else(|| panic!("{}() requires a value to search for, got: {:?}", func, needle));
+    if needle.len() != 1 && needle.len() != list.rows.len() {
+        panic!("{}() value column has {} rows, list has {}", func, needle.len(), list.rows.len());
+    }
+
+    let target = list.values.data_type();
+    let casted = arrow::compute::cast(&needle, target)
+        .unwrap_or_else(|_| arrow::array::new_null_array(target, needle.len()));
+    let needle_valid = arrow::compute::is_not_null(needle.as_ref()).unwrap();
+    let searchable = (0..needle.len()).map(|i| !needle_valid.value(i) || casted.is_valid(i)).collect();
+
+    let converter = RowConverter::new(vec![SortField::new(target.clone())])
+        .unwrap_or_else(|e| panic!("{}() not supported for {:?}: {}", func, target, e));
+    let values = converter.convert_columns(std::slice::from_ref(&list.values))
+        .unwrap_or_else(|e| panic!("{}() failed: {}", func, e));
+    let needles = converter.convert_columns(&[casted])
+        .unwrap_or_else(|e| panic!("{}() failed: {}", func, e));
+    (values, needles, searchable)
+}
+
+/// Per-row position of the needle in each list (None if the list is null, Some(None) if not found)
+fn list_positions(func: &str, list_arg: &EvalResult, needle: &EvalResult) -> Vec<Option<Option<usize>>> {
+    let list = list_rows(func, list_arg);
+    let (values, needles, searchable) = element_rows(func, &list, needle);
+    let needle_index = |row: usize| if needles.num_rows() == 1 { 0 } else { row };
+
+    list.rows.iter().enumerate()
+        .map(|(row, range)| {
+            range.clone().map(|range| {
+                let n = needle_index(row);
+                if !searchable[n] {
+                    return None;
+                }
+                range.into_iter().position(|i| values.row(i) == needles.row(n))
+            })
+        })
+        .collect()
+}
+
+/// Number of elements per row as Int32 (null lists stay null)
+fn list_lengths<'a>(func: &str, arg: &EvalResult) -> EvalResult<'a> {
+    let list = list_rows(func, arg);
+    let lengths: Int32Array = list.rows.iter().map(|r| r.as_ref().map(|r| r.len() as i32)).collect();
+    EvalResult::Int32Array(Cow::Owned(lengths))
+}
+
+/// unique(list) - removes duplicate elements per row (first occurrence wins)
+fn func_unique<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("unique() requires exactly 1 argument");
+    }
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    let list = list_rows("unique", &arg);
+    let encoded = row_encode("unique", &list.values);
+
+    let rows = list.rows.iter()
+        .map(|range| {
+            range.clone().map(|range| {
+                let mut seen = std::collections::HashSet::new();
+                range.filter(|&i| seen.insert(encoded.row(i))).collect()
+            })
+        })
+        .collect();
+    array_to_result(build_list(&list, &list.values, rows))
+}
+
+/// array_contains(list, value) - true if the row's list contains the value
+fn func_array_contains<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("array_contains() requires exactly 2 arguments");
+    }
+    let list_arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&list_arg]) {
+        return null;
+    }
+    let positions = list_positions("array_contains", &list_arg, &eval_jmespath(&args[1], rb));
+    let result: BooleanArray = positions.into_iter().map(|p| p.map(|p| p.is_some())).collect();
+    EvalResult::BoolArray(result)
+}
+
+/// array_position(list, value) - 0-based index of the first match per row, null if absent
+fn func_array_position<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("array_position() requires exactly 2 arguments");
+    }
+    let list_arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&list_arg]) {
+        return null;
+    }
+    let positions = list_positions("array_position", &list_arg, &eval_jmespath(&args[1], rb));
+    let result: Int32Array = positions.into_iter().map(|p| p.flatten().map(|p| p as i32)).collect();
+    EvalResult::Int32Array(Cow::Owned(result))
+}
+
+/// array_concat(list1, list2, ...) - concatenates the lists of each row (null lists count as empty)
+fn func_array_concat<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() < 2 {
+        panic!("array_concat() requires at least 2 arguments");
+    }
+    let results: Vec<EvalResult> = args.iter().map(|arg| eval_jmespath(arg, rb)).collect();
+    let list_args: Vec<&EvalResult> = results.iter().filter(|r| !is_null_input(r)).collect();
+    if list_args.is_empty() {
+        return null_propagation(&results.iter().collect::<Vec<_>>()).unwrap();
+    }
+    let lists: Vec<ListRows> = list_args.into_iter()
+        .map(|arg| list_rows("array_concat", arg))
+        .collect();
+    let num_rows = lists[0].rows.len();
+    if lists.iter().any(|l| l.rows.len() != num_rows) {
+        panic!("array_concat() requires lists with the same number of rows");
+    }
+
+    // All child values in one array (cast to the element type of the first list)
+    let target = lists[0].values.data_type().clone();
+    let values: Vec<ArrayRef> = lists.iter()
+        .map(|l| arrow::compute::cast(&l.values, &target)
+            .unwrap_or_else(|e| panic!("array_concat() cannot combine {:?} with {:?}: {}", target, l.values.data_type(), e)))
+        .collect();
+    let value_refs: Vec<&dyn Array> = values.iter().map(|v| v.as_ref()).collect();
+    let combined = arrow::compute::concat(&value_refs).unwrap();
+    let bases: Vec<usize> = values.iter()
+        .scan(0, |base, v| { let start = *base; *base += v.len(); Some(start) })
+        .collect();
+
+    let rows = (0..num_rows)
+        .map(|row| {
+            if lists.iter().all(|l| l.rows[row].is_none()) {
+                return None;
+            }
+            let indices = lists.iter().zip(&bases)
+                .filter_map(|(l, base)| l.rows[row].clone().map(|r| r.map(move |i| i + base)))
+                .flatten()
+                .collect();
+            Some(indices)
+        })
+        .collect();
+    array_to_result(build_list(&lists[0], &combined, rows))
+}
+
+/// array_slice(list, start, stop?, step?) - slices every row like list[start:stop:step]
+fn func_array_slice<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() < 2 || args.len() > 4 {
+        panic!("array_slice() requires 2 to 4 arguments");
+    }
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    let list = list_rows("array_slice", &arg);
+    let start = int_arg("array_slice", &args[1], rb);
+    let stop = args.get(2).map(|a| int_arg("array_slice", a, rb));
+    let step = args.get(3).map(|a| int_arg("array_slice", a, rb));
+
+    let rows = list.rows.iter()
+        .map(|range| {
+            range.clone().map(|range| {
+                slice_array(&|i| range.start + i, range.len(), Some(start), stop, step)
+            })
+        })
+        .collect();
+    array_to_result(build_list(&list, &list.values, rows))
+}
+
+/// array_sort(list, 'desc'?) - sorts the elements of each row (nulls last)
+fn func_array_sort<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::compute::{sort_to_indices, SortOptions};
+    if args.is_empty() || args.len() > 2 {
+        panic!("array_sort() requires 1 or 2 arguments");
+    }
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    let list = list_rows("array_sort", &arg);
+    let descending = match args.get(1).map(|a| string_literal_arg("array_sort", a, rb)) {
+        None => false,
+        Some(order) if order.eq_ignore_ascii_case("asc") => false,
+        Some(order) if order.eq_ignore_ascii_case("desc") => true,
+        Some(order) => panic!("array_sort() order must be 'asc' or 'desc', got '{}'", order),
+    };
+    let options = SortOptions { descending, nulls_first: false };
+
+    let rows = list.rows.iter()
+        .map(|range| {
+            range.clone().map(|range| {
+                let slice = list.values.slice(range.start, range.len());
+                let sorted = sort_to_indices(&slice, Some(options), None)
+                    .unwrap_or_else(|e| panic!("array_sort() not supported for {:?}: {}", slice.data_type(), e));
+                sorted.values().iter().map(|&i| range.start + i as usize).collect()
+            })
+        })
+        .collect();
+    array_to_result(build_list(&list, &list.values, rows))
+}
+
+/// array_sum(list) - sum of the non-null elements per row (Int64 for integers, Float64 otherwise)
+fn func_array_sum<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("array_sum() requires exactly 1 argument");
+    }
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    let list = list_rows("array_sum", &arg);
+    let element_type = list.values.data_type();
+    if !element_type.is_numeric() {
+        panic!("array_sum() requires a list of numbers, got: {:?}", element_type);
+    }
+
+    if element_type.is_integer() {
+        // Integers sum as Int64; elements beyond i64 and overflowing sums are errors
+        let options = arrow::compute::CastOptions { safe: false, ..Default::default() };
+        let values = arrow::compute::cast_with_options(&list.values, &DataType::Int64, &options)
+            .unwrap_or_else(|e| panic!("array_sum() overflowed Int64: {}", e));
+        let values = values.as_any().downcast_ref::<Int64Array>().unwrap();
+        let sums: Int64Array = list.rows.iter()
+            .map(|r| r.clone().map(|r| {
+                r.filter(|&i| values.is_valid(i))
+                    .try_fold(0i64, |total, i| total.checked_add(values.value(i)))
+                    .unwrap_or_else(|| panic!("array_sum() overflowed Int64"))
+            }))
+            .collect();
+        EvalResult::Int64Array(Cow::Owned(sums))
+    } else {
+        let values = arrow::compute::cast(&list.values, &DataType::Float64).unwrap();
+        let values = values.as_any().downcast_ref::<Float64Array>().unwrap();
+        let sums: Float64Array = list.rows.iter()
+            .map(|r| r.clone().map(|r| r.filter(|&i| values.is_valid(i)).map(|i| values.value(i)).sum()))
+            .collect();
+        EvalResult::Float64Array(Cow::Owned(sums))
+    }
+}
+
+/// array_length(list) - number of elements per row
+fn func_array_length<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("array_length() requires exactly 1 argument");
+    }
+    let arg = eval_jmespath(&args[0], rb);
+    if let Some(null) = null_propagation(&[&arg]) {
+        return null;
+    }
+    list_lengths("array_length", &arg)
+}
+
+// ============================================================================
+// MATH FUNCTIONS (abs, ceil, floor, round, sqrt, pow, log, exp, sign, clamp, mod)
+// ============================================================================
+
+/// Evaluate the numeric arguments of a math function as kernel operands
+fn math_operands(func: &str, args: &[JmesPathExpr], rb: &RecordBatch, arity: std::ops::RangeInclusive<usize>) -> Vec<Operand> {
+    if !arity.contains(&args.len()) {
+        if arity.start() == arity.end() {
+            panic!("{}() requires exactly {} argument(s)", func, arity.start());
+        }
+        panic!("{}() requires {} to {} arguments", func, arity.start(), arity.end());
+    }
+    args.iter()
+        .map(|arg| {
+            let operand = to_operand(func, &eval_jmespath(arg, rb));
+            let data_type = operand.array.data_type();
+            if !data_type.is_numeric() && data_type != &DataType::Null {
+                panic!("{}() requires numeric arguments, got: {:?}", func, data_type);
+            }
+            operand
+        })
+        .collect()
+}
+
+/// Cast an operand to Float64 (nulls are preserved)
+fn float64_operand(operand: &Operand) -> Operand {
+    let array = arrow::compute::cast(&operand.array, &DataType::Float64).unwrap();
+    Operand { array, is_scalar: operand.is_scalar }
+}
+
+/// Apply a float function; float inputs keep their type, integers produce Float64
+fn float_unary(operand: &Operand, f: impl Fn(f64) -> f64) -> ArrayRef {
+    use arrow::array::AsArray;
+    use arrow::datatypes::Float64Type;
+    let target = if operand.array.data_type().is_floating() {
+        operand.array.data_type().clone()
+    } else {
+        DataType::Float64
+    };
+    let values = float64_operand(operand).array;
+    let result: ArrayRef = Arc::new(values.as_primitive::<Float64Type>().unary::<_, Float64Type>(f));
+    arrow::compute::cast(&result, &target).unwrap()
+}
+
+/// Apply a binary float function to two Float64 operands (constants are not materialized)
+fn float_binary(a: &Operand, b: &Operand, f: impl Fn(f64, f64) -> f64) -> ArrayRef {
+    use arrow::array::AsArray;
+    use arrow::compute::binary;
+    use arrow::datatypes::Float64Type;
+    let (a, b) = (float64_operand(a), float64_operand(b));
+    let (x, y) = (a.array.as_primitive::<Float64Type>(), b.array.as_primitive::<Float64Type>());
+    let len = if a.is_scalar { y.len() } else { x.len() };
+
+    let result: Float64Array = match (a.is_scalar, b.is_scalar) {
+        (true, false) if x.is_valid(0) => { let c = x.value(0); y.unary(|v| f(c, v)) },
+        (false, true) if y.is_valid(0) => { let c = y.value(0); x.unary(|v| f(v, c)) },
+        (true, false) | (false, true) => Float64Array::new_null(len),
+        _ => binary(x, y, f).unwrap(),
+    };
+    Arc::new(result)
+}
+
+/// |v| for any numeric type (null if it does not fit, e.g. abs of i32::MIN)
+fn abs_kernel<T: arrow::datatypes::ArrowPrimitiveType>(array: &ArrayRef) -> ArrayRef {
+    use arrow::array::AsArray;
+    use arrow_array::ArrowNativeTypeOp;
+    let zero = T::Native::ZERO;
+    Arc::new(array.as_primitive::<T>().unary_opt::<_, T>(|v| if v.is_lt(zero) { v.neg_checked().ok() } else { Some(v) }))
+}
+
+/// -1, 0 or 1 for integer types
+fn sign_kernel<T: arrow::datatypes::ArrowPrimitiveType>(array: &ArrayRef) -> ArrayRef {
+    use arrow::array::AsArray;
+    use arrow_array::ArrowNativeTypeOp;
+    let (zero, one) = (T::Native::ZERO, T::Native::ONE);
+    Arc::new(array.as_primitive::<T>().unary::<_, T>(|v| {
+        if v.is_zero() { zero } else if v.is_lt(zero) { one.neg_wrapping() } else { one }
+    }))
+}
+
+/// min(max(x, lo), hi) per row; all operands already have type T and equal length
+fn clamp_kernel<T: arrow::datatypes::ArrowPrimitiveType>(x: &ArrayRef, lo: &ArrayRef, hi: &ArrayRef) -> ArrayRef {
+    use arrow::array::{AsArray, PrimitiveArray};
+    use arrow::compute::binary;
+    use arrow_array::ArrowNativeTypeOp;
+    let lower: PrimitiveArray<T> = binary(x.as_primitive::<T>(), lo.as_primitive::<T>(), |v, lo| if v.is_lt(lo) { lo } else { v }).unwrap();
+    let clamped: PrimitiveArray<T> = binary(&lower, hi.as_primitive::<T>(), |v, hi| if hi.is_lt(v) { hi } else { v }).unwrap();
+    Arc::new(clamped)
+}
+
+/// Remainder per row (sign follows the dividend); division by zero yields null
+fn mod_kernel<T: arrow::datatypes::ArrowPrimitiveType>(a: &ArrayRef, b: &ArrayRef) -> ArrayRef {
+    use arrow::array::{AsArray, PrimitiveArray};
+    use arrow::buffer::NullBuffer;
+    use arrow::compute::binary;
+    use arrow_array::ArrowNativeTypeOp;
+    let (a, b) = (a.as_primitive::<T>(), b.as_primitive::<T>());
+    let result: PrimitiveArray<T> = binary(a, b, |x, y| if y.is_zero() { T::Native::ZERO } else { x.mod_wrapping(y) }).unwrap();
+    let non_zero = NullBuffer::from(b.values().iter().map(|y| !y.is_zero()).collect::<Vec<bool>>());
+    let nulls = NullBuffer::union(result.nulls(), Some(&non_zero));
+    Arc::new(PrimitiveArray::<T>::new(result.values().clone(), nulls))
+}
+
+/// abs(number) - absolute value (nulls stay null, values without one like i32::MIN become null)
+fn func_abs<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let x = math_operands("abs", args, rb, 1..=1).remove(0);
+    if x.array.data_type() == &DataType::Null {
+        return operand_result(x.array, x.is_scalar);
+    }
+    let result = numeric_kernel!("abs", x.array.data_type(), abs_kernel(&x.array));
+    operand_result(result, x.is_scalar)
+}
+
+/// ceil(number) - smallest integer value >= x (integers are returned unchanged)
+fn func_ceil<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let x = math_operands("ceil", args, rb, 1..=1).remove(0);
+    if x.array.data_type() == &DataType::Null {
+        return operand_result(x.array, x.is_scalar);
+    }
+    if x.array.data_type().is_integer() {
+        return operand_result(x.array, x.is_scalar);
+    }
+    operand_result(float_unary(&x, f64::ceil), x.is_scalar)
+}
+
+/// floor(number) - largest integer value <= x (integers are returned unchanged)
+fn func_floor<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let x = math_operands("floor", args, rb, 1..=1).remove(0);
+    if x.array.data_type() == &DataType::Null {
+        return operand_result(x.array, x.is_scalar);
+    }
+    if x.array.data_type().is_integer() {
+        return operand_result(x.array, x.is_scalar);
+    }
+    operand_result(float_unary(&x, f64::floor), x.is_scalar)
+}
+
+/// round(number, digits?) - rounds half away from zero; negative digits round to tens, hundreds, ...
+/// Example: round(price, 2)
+fn func_round<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.is_empty() || args.len() > 2 {
+        panic!("round() requires 1 or 2 arguments");
+    }
+    let x = math_operands("round", &args[..1], rb, 1..=1).remove(0);
+    let digits = args.get(1).map_or(0, |arg| int_arg("round", arg, rb));
+    if x.array.data_type().is_integer() && digits >= 0 {
+        return operand_result(x.array, x.is_scalar);
+    }
+
+    let factor = 10f64.powi(digits);
+    let rounded = float_unary(&x, |v| (v * factor).round() / factor);
+    // Integers stay integers when rounding to tens, hundreds, ...
+    let result = if x.array.data_type().is_integer() {
+        arrow::compute::cast(&rounded, x.array.data_type()).unwrap()
+    } else {
+        rounded
+    };
+    operand_result(result, x.is_scalar)
+}
+
+/// sqrt(number) - square root (negative input gives NaN)
+fn func_sqrt<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let x = math_operands("sqrt", args, rb, 1..=1).remove(0);
+    operand_result(float_unary(&x, f64::sqrt), x.is_scalar)
+}
+
+/// exp(number) - e raised to x
+fn func_exp<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let x = math_operands("exp", args, rb, 1..=1).remove(0);
+    operand_result(float_unary(&x, f64::exp), x.is_scalar)
+}
+
+/// log(number, base?) - natural logarithm, or logarithm to the given base
+fn func_log<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let operands = math_operands("log", args, rb, 1..=2);
+    let all_scalar = operands.iter().all(|o| o.is_scalar);
+    let result = match operands.as_slice() {
+        [x] => float_unary(x, f64::ln),
+        [x, base] => float_binary(x, base, f64::log),
+        _ => unreachable!(),
+    };
+    operand_result(result, all_scalar)
+}
+
+/// pow(base, exponent) - base raised to exponent (Float64)
+fn func_pow<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let operands = math_operands("pow", args, rb, 2..=2);
+    let all_scalar = operands.iter().all(|o| o.is_scalar);
+    operand_result(float_binary(&operands[0], &operands[1], f64::powf), all_scalar)
+}
+
+/// sign(number) - -1, 0 or 1 in the type of the input (NaN stays NaN)
+fn func_sign<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let x = math_operands("sign", args, rb, 1..=1).remove(0);
+    if x.array.data_type() == &DataType::Null {
+        return operand_result(x.array, x.is_scalar);
+    }
+    let result = if x.array.data_type().is_floating() {
+        float_unary(&x, |v| if v == 0.0 || v.is_nan() { v } else { v.signum() })
+    } else {
+        numeric_kernel!("sign", x.array.data_type(), sign_kernel(&x.array))
+    };
+    operand_result(result, x.is_scalar)
+}
+
+/// clamp(number, lo, hi) - limits each value to [lo, hi]
+/// Example: clamp(score, 0, 100)
+fn func_clamp<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let operands = unify_numeric_operands("clamp", math_operands("clamp", args, rb, 3..=3));
+    let all_scalar = operands.iter().all(|o| o.is_scalar);
+    let len = operands_len(&operands);
+    let [x, lo, hi] = [0, 1, 2].map(|i| broadcast(&operands[i], len));
+    let result = numeric_kernel!("clamp", x.data_type(), clamp_kernel(&x, &lo, &hi));
+    operand_result(result, all_scalar)
+}
+
+/// mod(a, b) - remainder of a / b (null when b is 0)
+fn func_mod<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    let operands = unify_numeric_operands("mod", math_operands("mod", args, rb, 2..=2));
+    let all_scalar = operands.iter().all(|o| o.is_scalar);
+    let len = operands_len(&operands);
+    let (a, b) = (broadcast(&operands[0], len), broadcast(&operands[1], len));
+    let result = numeric_kernel!("mod", a.data_type(), mod_kernel(&a, &b));
+    operand_result(result, all_scalar)
+}
+
+// ============================================================================
+// ROW SELECTION FUNCTIONS (top_k, bottom_k, distinct, distinct_by, unnest, explode)
+// ============================================================================
+
+/// Evaluate the batch argument of a row function (falls back to the current batch like sort_by)
+fn batch_arg(arg: &JmesPathExpr, rb: &RecordBatch) -> RecordBatch {
+    match eval_jmespath(arg, rb) {
+        EvalResult::RecordBatch(batch) => batch,
+        _ => rb.clone(),
+    }
+}
+
+/// Evaluate an expression reference (&key) against every row of the batch
+fn key_column(func: &str, arg: &JmesPathExpr, batch: &RecordBatch) -> ArrayRef {
+    let JmesPathExpr::ExprRef(inner) = arg else {
+        panic!("{}() requires an expression reference (&field) as key", func);
+    };
+    let key = eval_jmespath(inner, batch);
+    let key = result_to_array(&key)
+        .unwrap_or_else(|| panic!("{}() key must evaluate to a column, got: {:?}", func, key));
+    if key.len() != batch.num_rows() {
+        panic!("{}() key has {} rows, batch has {}", func, key.len(), batch.num_rows());
+    }
+    key
+}
+
+/// Output column name of a field reference (&orders, &customer.orders -> "orders")
+fn ref_field_name(func: &str, arg: &JmesPathExpr) -> String {
+    match arg {
+        JmesPathExpr::ExprRef(inner) => match inner.as_ref() {
+            JmesPathExpr::Path(parts) if !parts.is_empty() => parts[parts.len() - 1].clone(),
+            _ => panic!("{}() requires a field reference like &orders", func),
+        },
+        _ => panic!("{}() requires an expression reference (&field) as key", func),
+    }
+}
+
+/// Pick rows by index from every column
+fn take_rows(func: &str, batch: &RecordBatch, indices: &dyn Array) -> RecordBatch {
+    arrow::compute::take_record_batch(batch, indices)
+        .unwrap_or_else(|e| panic!("{}() failed to select rows: {}", func, e))
+}
+
+/// Indices of the first row for every distinct encoded row (in input order)
+fn first_occurrences(func: &str, columns: &[ArrayRef]) -> arrow::array::UInt32Array {
+    use arrow::row::{RowConverter, SortField};
+    let fields = columns.iter().map(|c| SortField::new(c.data_type().clone())).collect();
+    let converter = RowConverter::new(fields)
+        .unwrap_or_else(|e| panic!("{}() not supported for these columns: {}", func, e));
+    let rows = converter.convert_columns(columns)
+        .unwrap_or_else(|e| panic!("{}() failed: {}", func, e));
+
+    let mut seen = std::collections::HashSet::new();
+    (0..rows.num_rows())
+        .filter(|&i| seen.insert(rows.row(i)))
+        .map(|i| i as u32)
+        .collect()
+}
+
+/// Shared implementation of unnest/explode: one output row per list element, parent columns repeated via take
+fn unnest_rows<'a>(func: &str, args: &[JmesPathExpr], rb: &'a RecordBatch, keep_empty: bool) -> EvalResult<'a> {
+    use arrow::array::UInt32Array;
+    use arrow::datatypes::{Field, Schema};
+    if args.len() != 2 {
+        panic!("{}() requires exactly 2 arguments: {}(array, &list_field)", func, func);
+    }
+    let batch = batch_arg(&args[0], rb);
+    let name = ref_field_name(func, &args[1]);
+    let list = list_rows_from_array(func, &key_column(func, &args[1], &batch));
+
+    // Parent row and element index for every output row (no element: null row in explode mode)
+    let mut parents = Vec::new();
+    let mut elements = Vec::new();
+    for (row, range) in list.rows.iter().enumerate() {
+        match range {
+            Some(range) if !range.is_empty() => {
+                parents.extend(std::iter::repeat_n(row as u32, range.len()));
+                elements.extend(range.clone().map(|i| Some(i as u32)));
+            },
+            _ if keep_empty => {
+                parents.push(row as u32);
+                elements.push(None);
+            },
+            _ => {},
+        }
+    }
+
+    let repeated = take_rows(func, &batch, &UInt32Array::from(parents));
+    let values = arrow::compute::take(list.values.as_ref(), &UInt32Array::from(elements), None)
+        .unwrap_or_else(|e| panic!("{}() failed to take list elements: {}", func, e));
+
+    // The list column is replaced by its elements (or appended for nested paths)
+    let mut fields: Vec<Field> = repeated.schema().fields().iter().map(|f| f.as_ref().clone()).collect();
+    let mut columns = repeated.columns().to_vec();
+    let element_field = Field::new(&name, values.data_type().clone(), true);
+    match fields.iter().position(|f| f.name() == &name) {
+        Some(pos) => {
+            fields[pos] = element_field;
+            columns[pos] = values;
+        },
+        None => {
+            fields.push(element_field);
+            columns.push(values);
+        },
+    }
+    EvalResult::RecordBatch(RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap())
+}
+
+/// unnest(array, &list_field) - one row per list element, parent columns repeated;
+/// rows with null or empty lists are dropped
+/// Example: unnest(@, &orders) | orders[?orders.amount > 100]
+fn func_unnest<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    unnest_rows("unnest", args, rb, false)
+}
+
+/// explode(array, &list_field) - like unnest, but null/empty lists keep one row with a null element
+fn func_explode<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    unnest_rows("explode", args, rb, true)
+}
+
+/// Shared implementation of top_k/bottom_k: partial sort on the key, nulls last
+fn select_k<'a>(func: &str, args: &[JmesPathExpr], rb: &'a RecordBatch, descending: bool) -> EvalResult<'a> {
+    use arrow::compute::{sort_to_indices, SortOptions};
+    if args.len() != 3 {
+        panic!("{}() requires exactly 3 arguments: {}(array, &expr, n)", func, func);
+    }
+    let batch = batch_arg(&args[0], rb);
+    let key = key_column(func, &args[1], &batch);
+    let n = int_arg(func, &args[2], rb);
+    if n < 0 {
+        panic!("{}() requires n >= 0, got {}", func, n);
+    }
+
+    // With a limit, sort_to_indices only partially sorts the key column
+    let options = SortOptions { descending, nulls_first: false };
+    let indices = sort_to_indices(&key, Some(options), Some(n as usize))
+        .unwrap_or_else(|e| panic!("{}() cannot sort by {:?}: {}", func, key.data_type(), e));
+    EvalResult::RecordBatch(take_rows(func, &batch, &indices))
+}
+
+/// top_k(array, &expr, n) - the n rows with the largest key (descending)
+/// Example: top_k(@, &score, 10)
+fn func_top_k<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    select_k("top_k", args, rb, true)
+}
+
+/// bottom_k(array, &expr, n) - the n rows with the smallest key (ascending)
+fn func_bottom_k<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    select_k("bottom_k", args, rb, false)
+}
+
+/// distinct(array) - removes duplicate rows (first occurrence wins, order is kept)
+fn func_distinct<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 1 {
+        panic!("distinct() requires exactly 1 argument");
+    }
+    let batch = batch_arg(&args[0], rb);
+    if batch.num_columns() == 0 {
+        return EvalResult::RecordBatch(batch);
+    }
+    let indices = first_occurrences("distinct", batch.columns());
+    EvalResult::RecordBatch(take_rows("distinct", &batch, &indices))
+}
+
+/// distinct_by(array, &expr) - keeps the first row for every distinct key
+/// Example: distinct_by(@, &user_id)
+fn func_distinct_by<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("distinct_by() requires exactly 2 arguments: distinct_by(array, &expr)");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let key = key_column("distinct_by", &args[1], &batch);
+    let indices = first_occurrences("distinct_by", &[key]);
+    EvalResult::RecordBatch(take_rows("distinct_by", &batch, &indices))
+}
+
+// ============================================================================
+// RESHAPING FUNCTIONS (pivot, unpivot)
+// ============================================================================
+
+/// Concatenate single-value results into one column of their common type (empty cells are null)
+fn constants_to_column(func: &str, cells: &[EvalResult]) -> ArrayRef {
+    let operands = cells.iter()
+        .map(|cell| Operand {
+            array: const_to_array(cell)
+                .unwrap_or_else(|| panic!("{}() aggregate must produce a single value, got: {:?}", func, cell)),
+            is_scalar: true,
+        })
+        .collect();
+    let operands = unify_operands(func, operands);
+    let arrays: Vec<&dyn Array> = operands.iter().map(|o| o.array.as_ref()).collect();
+    arrow::compute::concat(&arrays).unwrap()
+}
+
+/// pivot(array, &row_key, &column_key, &aggregate) - one row per row key, one column per distinct column key
+/// Example: pivot(@, &region, &month, &sum(revenue))
+fn func_pivot<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::array::UInt32Array;
+    use arrow::datatypes::{Field, Schema};
+    if args.len() != 4 {
+        panic!("pivot() requires exactly 4 arguments: pivot(array, &row_key, &column_key, &aggregate)");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let row_name = ref_field_name("pivot", &args[1]);
+    let (row_keys, row_group) = group_keys("pivot", &key_column("pivot", &args[1], &batch));
+    let (column_keys, column_group) = group_keys("pivot", &key_column("pivot", &args[2], &batch));
+    let JmesPathExpr::ExprRef(aggregate) = &args[3] else {
+        panic!("pivot() requires an expression reference as aggregate, e.g. &sum(revenue)");
+    };
+
+    // Rows of every (row key, column key) cell
+    let mut cells: Vec<Vec<Vec<u32>>> = vec![vec![Vec::new(); column_keys.len()]; row_keys.len()];
+    for (i, (r, c)) in row_group.iter().zip(&column_group).enumerate() {
+        if let (Some(r), Some(c)) = (r, c) {
+            cells[*r][*c].push(i as u32);
+        }
+    }
+
+    let column_names = arrow::compute::cast(&column_keys, &DataType::Utf8)
+        .unwrap_or_else(|e| panic!("pivot() cannot use {:?} values as column names: {}", column_keys.data_type(), e));
+    let column_names = column_names.as_any().downcast_ref::<StringArray>().unwrap();
+
+    let mut fields = vec![Field::new(&row_name, row_keys.data_type().clone(), true)];
+    let mut columns = vec![row_keys.clone()];
+    for c in 0..column_keys.len() {
+        let values: Vec<EvalResult> = cells.iter()
+            .map(|row| {
+                if row[c].is_empty() {
+                    return EvalResult::NullConst;
+                }
+                let cell = take_rows("pivot", &batch, &UInt32Array::from(row[c].clone()));
+                // Keep only an owned constant, the cell batch is dropped afterwards
+                let result = eval_jmespath(aggregate, &cell);
+                const_to_array(&result)
+                    .map(|arr| scalar_from_array(arr.as_ref(), 0))
+                    .unwrap_or_else(|| panic!("pivot() aggregate must produce a single value, got: {:?}", result))
+            })
+            .collect();
+        let column = constants_to_column("pivot", &values);
+        fields.push(Field::new(column_names.value(c), column.data_type().clone(), true));
+        columns.push(column);
+    }
+
+    EvalResult::RecordBatch(RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap())
+}
+
+/// unpivot(array, ['c1', 'c2'], 'name_column', 'value_column') - turns columns into rows;
+/// the other columns are repeated for every unpivoted column
+/// Example: unpivot(@, ['q1', 'q2', 'q3'], 'quarter', 'value')
+fn func_unpivot<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::array::UInt32Array;
+    use arrow::datatypes::{Field, Schema};
+    if args.len() != 4 {
+        panic!("unpivot() requires exactly 4 arguments: unpivot(array, ['col', ...], 'name', 'value')");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let JmesPathExpr::MultiSelectList(items) = &args[1] else {
+        panic!("unpivot() requires a list of column names like ['q1', 'q2']");
+    };
+    let names: Vec<String> = items.iter()
+        .map(|item| match item {
+            JmesPathExpr::ConstString(name) => name.clone(),
+            JmesPathExpr::Path(parts) if parts.len() == 1 => parts[0].clone(),
+            other => panic!("unpivot() column names must be strings, got: {:?}", other),
+        })
+        .collect();
+    if names.is_empty() {
+        panic!("unpivot() requires at least one column");
+    }
+    let name_column = string_literal_arg("unpivot", &args[2], rb);
+    let value_column = string_literal_arg("unpivot", &args[3], rb);
+
+    let schema = batch.schema();
+    let unpivoted: Vec<Operand> = names.iter()
+        .map(|name| {
+            let idx = schema.index_of(name)
+                .unwrap_or_else(|_| panic!("unpivot() field '{}' not found in RecordBatch", name));
+            Operand { array: batch.column(idx).clone(), is_scalar: false }
+        })
+        .collect();
+    let unpivoted = unify_operands("unpivot", unpivoted);
+
+    // Output row j * k + c holds input row j, unpivoted column c
+    let (rows, k) = (batch.num_rows(), names.len());
+    let kept: Vec<usize> = (0..schema.fields().len())
+        .filter(|&i| !names.contains(schema.field(i).name()))
+        .collect();
+    let kept = batch.project(&kept).unwrap();
+    let repeated = take_rows("unpivot", &kept, &UInt32Array::from_iter_values((0..rows as u32).flat_map(|j| std::iter::repeat_n(j, k))));
+
+    let labels: StringArray = (0..rows).flat_map(|_| names.iter().map(Some)).collect();
+    let arrays: Vec<&dyn Array> = unpivoted.iter().map(|o| o.array.as_ref()).collect();
+    let stacked = arrow::compute::concat(&arrays).unwrap();
+    let interleave = UInt32Array::from_iter_values((0..rows).flat_map(|j| (0..k).map(move |c| (c * rows + j) as u32)));
+    let values = arrow::compute::take(stacked.as_ref(), &interleave, None).unwrap();
+
+    let mut fields: Vec<Field> = kept.schema().fields().iter().map(|f| f.as_ref().clone()).collect();
+    let mut columns = repeated.columns().to_vec();
+    fields.push(Field::new(&name_column, DataType::Utf8, false));
+    columns.push(Arc::new(labels));
+    fields.push(Field::new(&value_column, values.data_type().clone(), true));
+    columns.push(values);
+
+    EvalResult::RecordBatch(RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap())
+}
+
+// ============================================================================
+// SAMPLING FUNCTIONS (limit, offset, sample, sample_fraction)
+// ============================================================================
+
+/// Non-negative row count argument, clamped to the batch size
+fn row_count_arg(func: &str, arg: &JmesPathExpr, rb: &RecordBatch, num_rows: usize) -> usize {
+    let n = int_arg(func, arg, rb);
+    if n < 0 {
+        panic!("{}() requires a non-negative row count, got {}", func, n);
+    }
+    (n as usize).min(num_rows)
+}
+
+/// Deterministic pseudo-random value of a row for a seed (uniform over u64)
+fn row_hash(row: usize, seed: i32) -> u64 {
+    xxhash_rust::xxh64::xxh64(&(row as u64).to_le_bytes(), seed as u64)
+}
+
+/// Optional seed argument (default 0)
+fn seed_arg(func: &str, args: &[JmesPathExpr], idx: usize, rb: &RecordBatch) -> i32 {
+    args.get(idx).map(|arg| int_arg(func, arg, rb)).unwrap_or(0)
+}
+
+/// limit(array, n) - first n rows (zero-copy slice)
+/// Example: limit(sort_by(@, &price), 10)
+fn func_limit<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("limit() requires exactly 2 arguments: limit(array, n)");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let n = row_count_arg("limit", &args[1], rb, batch.num_rows());
+    EvalResult::RecordBatch(batch.slice(0, n))
+}
+
+/// offset(array, n) - all rows after the first n (zero-copy slice)
+/// Example: limit(offset(@, 20), 10) - third page of 10 rows
+fn func_offset<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("offset() requires exactly 2 arguments: offset(array, n)");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let n = row_count_arg("offset", &args[1], rb, batch.num_rows());
+    EvalResult::RecordBatch(batch.slice(n, batch.num_rows() - n))
+}
+
+/// sample(array, n, seed?) - n rows chosen pseudo-randomly, same seed gives the same rows;
+/// rows keep their input order
+/// Example: sample(@, 100, 42)
+fn func_sample<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::array::UInt32Array;
+    if args.len() < 2 || args.len() > 3 {
+        panic!("sample() requires 2 or 3 arguments: sample(array, n, seed?)");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let n = row_count_arg("sample", &args[1], rb, batch.num_rows());
+    let seed = seed_arg("sample", args, 2, rb);
+    if n == batch.num_rows() {
+        return EvalResult::RecordBatch(batch);
+    }
+
+    let mut rows: Vec<usize> = (0..batch.num_rows()).collect();
+    rows.select_nth_unstable_by_key(n, |&row| row_hash(row, seed));
+    rows.truncate(n);
+    rows.sort_unstable();
+    let indices = UInt32Array::from_iter_values(rows.into_iter().map(|row| row as u32));
+    EvalResult::RecordBatch(take_rows("sample", &batch, &indices))
+}
+
+/// sample_fraction(array, fraction, seed?) - keeps every row with probability `fraction`
+/// Example: sample_fraction(@, 0.01, 42)
+fn func_sample_fraction<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.len() < 2 || args.len() > 3 {
+        panic!("sample_fraction() requires 2 or 3 arguments: sample_fraction(array, fraction, seed?)");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let fraction = match eval_jmespath(&args[1], rb) {
+        EvalResult::FloatConst(v) => v,
+        EvalResult::IntConst(v) => v as f64,
+        other => panic!("sample_fraction() expects a numeric fraction, got {:?}", other),
+    };
+    if !(0.0..=1.0).contains(&fraction) {
+        panic!("sample_fraction() fraction must be between 0 and 1, got {}", fraction);
+    }
+    let seed = seed_arg("sample_fraction", args, 2, rb);
+
+    let mask: BooleanArray = (0..batch.num_rows())
+        .map(|row| Some(fraction >= 1.0 || (row_hash(row, seed) as f64) < fraction * u64::MAX as f64))
+        .collect();
+    EvalResult::RecordBatch(filter_record_batch(&batch, &mask).unwrap())
+}
+
+// ============================================================================
+// WINDOW FUNCTIONS (with_columns, row_number, rank, dense_rank, lag, lead, running_sum)
+// ============================================================================
+
+/// with_columns(array, {name: expr, ...}) - adds computed columns, existing names are replaced in place
+/// Example: with_columns(@, {rn: row_number(&user, &ts), prev: lag(&value, `1`)})
+fn func_with_columns<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::datatypes::{Field, Schema};
+    if args.len() != 2 {
+        panic!("with_columns() requires exactly 2 arguments: with_columns(array, {{name: expr}})");
+    }
+    let batch = batch_arg(&args[0], rb);
+    let JmesPathExpr::MultiSelectHash(pairs) = &args[1] else {
+        panic!("with_columns() requires a multi-select hash like {{name: expr}}");
+    };
+
+    let schema = batch.schema();
+    let mut fields: Vec<Field> = schema.fields().iter().map(|f| f.as_ref().clone()).collect();
+    let mut columns = batch.columns().to_vec();
+    for (name, expr) in pairs {
+        let result = eval_jmespath(expr, &batch);
+        let column = match const_to_array(&result) {
+            Some(scalar) => broadcast(&Operand { array: scalar, is_scalar: true }, batch.num_rows()),
+            None => result_to_array(&result)
+                .unwrap_or_else(|| panic!("with_columns() cannot use {:?} as column '{}'", result, name)),
+        };
+        if column.len() != batch.num_rows() {
+            panic!("with_columns() column '{}' has {} rows, batch has {}", name, column.len(), batch.num_rows());
+        }
+        let field = Field::new(name, column.data_type().clone(), true);
+        match fields.iter().position(|f| f.name() == name) {
+            Some(idx) => {
+                fields[idx] = field;
+                columns[idx] = column;
+            }
+            None => {
+                fields.push(field);
+                columns.push(column);
+            }
+        }
+    }
+
+    EvalResult::RecordBatch(RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap())
+}
+
+/// Rows in window order (partition key, order key, input position) with a flag per position
+/// marking the first row of every partition
+struct WindowFrame {
+    order: Vec<usize>,
+    starts: Vec<bool>,
+}
+
+impl WindowFrame {
+    fn new(func: &str, partition: Option<&ArrayRef>, order: Option<&ArrayRef>, len: usize) -> Self {
+        use arrow::array::UInt32Array;
+        use arrow::compute::{lexsort_to_indices, SortColumn, SortOptions};
+        let options = Some(SortOptions { descending: false, nulls_first: false });
+        let position: ArrayRef = Arc::new(UInt32Array::from_iter_values(0..len as u32));
+        let columns: Vec<SortColumn> = partition.into_iter().chain(order).chain([&position])
+            .map(|values| SortColumn { values: values.clone(), options })
+            .collect();
+        let indices = lexsort_to_indices(&columns, None)
+            .unwrap_or_else(|e| panic!("{}() cannot order rows by these keys: {}", func, e));
+        let order: Vec<usize> = indices.values().iter().map(|&i| i as usize).collect();
+
+        let starts = match partition {
+            Some(key) => {
+                let encoded = row_encode(func, key);
+                (0..len).map(|pos| pos == 0 || encoded.row(order[pos]) != encoded.row(order[pos - 1])).collect()
+            }
+            None => (0..len).map(|pos| pos == 0).collect(),
+        };
+        WindowFrame { order, starts }
+    }
+
+    /// Partition number of every position in window order
+    fn partition_ids(&self) -> Vec<usize> {
+        self.starts.iter()
+            .scan(0, |id, &start| {
+                if start {
+                    *id += 1;
+                }
+                Some(*id)
+            })
+            .collect()
+    }
+}
+
+/// Evaluate the optional expression references at args[from..] (partition key, order key)
+fn window_keys(func: &str, args: &[JmesPathExpr], rb: &RecordBatch) -> (Option<ArrayRef>, Option<ArrayRef>) {
+    if args.len() > 2 {
+        panic!("{}() takes at most a partition and an order key", func);
+    }
+    let partition = args.first().map(|arg| key_column(func, arg, rb));
+    let order = args.get(1).map(|arg| key_column(func, arg, rb));
+    (partition, order)
+}
+
+/// row_number(&partition, &order?) - 1-based position of the row within its partition
+/// Example: with_columns(@, {rn: row_number(&user, &ts)})
+fn func_row_number<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    if args.is_empty() || args.len() > 2 {
+        panic!("row_number() requires 1 or 2 arguments: row_number(&partition, &order?)");
+    }
+    let (partition, order) = window_keys("row_number", args, rb);
+    let frame = WindowFrame::new("row_number", partition.as_ref(), order.as_ref(), rb.num_rows());
+
+    let mut numbers = vec![0i64; rb.num_rows()];
+    let mut n = 0;
+    for (pos, &row) in frame.order.iter().enumerate() {
+        n = if frame.starts[pos] { 1 } else { n + 1 };
+        numbers[row] = n;
+    }
+    array_to_result(Arc::new(Int64Array::from(numbers)))
+}
+
+/// Shared implementation of rank() and dense_rank(): equal order keys share a rank
+fn ranks<'a>(func: &str, args: &[JmesPathExpr], rb: &'a RecordBatch, dense: bool) -> EvalResult<'a> {
+    if args.len() != 2 {
+        panic!("{}() requires exactly 2 arguments: {}(&partition, &order)", func, func);
+    }
+    let (partition, order) = window_keys(func, args, rb);
+    let order = order.unwrap();
+    let frame = WindowFrame::new(func, partition.as_ref(), Some(&order), rb.num_rows());
+    let peers = row_encode(func, &order);
+
+    let mut ranks = vec![0i64; rb.num_rows()];
+    let (mut rank, mut seen) = (0, 0);
+    for (pos, &row) in frame.order.iter().enumerate() {
+        if frame.starts[pos] {
+            (rank, seen) = (1, 1);
+        } else {
+            seen += 1;
+            if peers.row(row) != peers.row(frame.order[pos - 1]) {
+                rank = if dense { rank + 1 } else { seen };
+            }
+        }
+        ranks[row] = rank;
+    }
+    array_to_result(Arc::new(Int64Array::from(ranks)))
+}
+
+/// rank(&partition, &order) - rank within the partition, ties share a rank and leave gaps (1, 1, 3)
+fn func_rank<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    ranks("rank", args, rb, false)
+}
+
+/// dense_rank(&partition, &order) - like rank() without gaps (1, 1, 2)
+fn func_dense_rank<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    ranks("dense_rank", args, rb, true)
+}
+
+/// Shared implementation of lag() and lead(): value of the row `offset` positions before/after
+/// in window order, null outside the partition
+fn shifted<'a>(func: &str, args: &[JmesPathExpr], rb: &'a RecordBatch, forward: bool) -> EvalResult<'a> {
+    use arrow::array::UInt32Array;
+    if args.is_empty() {
+        panic!("{}() requires at least 1 argument: {}(&value, offset?, &partition?, &order?)", func, func);
+    }
+    let values = key_column(func, &args[0], rb);
+    let (offset, keys) = match args.get(1) {
+        Some(arg) if !matches!(arg, JmesPathExpr::ExprRef(_)) => (int_arg(func, arg, rb), &args[2..]),
+        _ => (1, &args[1..]),
+    };
+    if offset < 0 {
+        panic!("{}() offset must not be negative, got {}", func, offset);
+    }
+    let (partition, order) = window_keys(func, keys, rb);
+    let frame = WindowFrame::new(func, partition.as_ref(), order.as_ref(), rb.num_rows());
+    let partitions = frame.partition_ids();
+
+    let offset = offset as usize;
+    let mut indices: Vec<Option<u32>> = vec![None; rb.num_rows()];
+    for (pos, &row) in frame.order.iter().enumerate() {
+        let source = if forward { pos.checked_add(offset) } else { pos.checked_sub(offset) };
+        indices[row] = source
+            .filter(|&src| src < partitions.len() && partitions[src] == partitions[pos])
+            .map(|src| frame.order[src] as u32);
+    }
+    let result = arrow::compute::take(values.as_ref(), &UInt32Array::from(indices), None)
+        .unwrap_or_else(|e| panic!("{}() failed: {}", func, e));
+    array_to_result(result)
+}
+
+/// lag(&value, offset?, &partition?, &order?) - value of the previous row (default offset 1)
+/// Example: with_columns(@, {prev: lag(&value, `1`, &user, &ts)})
+fn func_lag<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    shifted("lag", args, rb, false)
+}
+
+/// lead(&value, offset?, &partition?, &order?) - value of the next row (default offset 1)
+fn func_lead<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    shifted("lead", args, rb, true)
+}
+
+/// Cumulative sum in window order, nulls are skipped (null until the first value)
+fn running_total<T: arrow::datatypes::ArrowPrimitiveType>(values: &arrow::array::PrimitiveArray<T>, frame: &WindowFrame) -> ArrayRef {
+    use arrow_array::ArrowNativeTypeOp;
+    let mut totals: Vec<Option<T::Native>> = vec![None; values.len()];
+    let mut total: Option<T::Native> = None;
+    for (pos, &row) in frame.order.iter().enumerate() {
+        if frame.starts[pos] {
+            total = None;
+        }
+        if values.is_valid(row) {
+            total = Some(total.unwrap_or(T::Native::ZERO).add_checked(values.value(row))
+                .unwrap_or_else(|e| panic!("running_sum() overflowed {:?}: {}", T::DATA_TYPE, e)));
+        }
+        totals[row] = total;
+    }
+    Arc::new(totals.into_iter().collect::<arrow::array::PrimitiveArray<T>>())
+}
+
+/// running_sum(&value, &partition?, &order?) - cumulative sum within the partition
+/// (integers sum as Int64, floats as Float64)
+/// Example: with_columns(@, {balance: running_sum(&amount, &account, &ts)})
+fn func_running_sum<'a>(args: &[JmesPathExpr], rb: &'a RecordBatch) -> EvalResult<'a> {
+    use arrow::array::AsArray;
+    use arrow::datatypes::{Float64Type, Int64Type};
+    if args.is_empty() {
+        panic!("running_sum() requires at least 1 argument: running_sum(&value, &partition?, &order?)");
+    }
+    let values = key_column("running_sum", &args[0], rb);
+    let (partition, order) = window_keys("running_sum", &args[1..], rb);
+    let frame = WindowFrame::new("running_sum", partition.as_ref(), order.as_ref(), rb.num_rows());
+
+    let target = match values.data_type() {
+        t if t.is_integer() => DataType::Int64,
+        t if t.is_floating() => DataType::Float64,
+        other => panic!("running_sum() requires a numeric argument, got {:?}", other),
+    };
+    let values = arrow::compute::cast(&values, &target).unwrap();
+    let totals = match target {
+        DataType::Int64 => running_total(values.as_primitive::<Int64Type>(), &frame),
+        _ => running_total(values.as_primitive::<Float64Type>(), &frame),
+    };
+    array_to_result(totals)
+}
+
+// ============================================================================
+// DICTIONARY FAST PATHS
+// ============================================================================
+
+/// Functions that map every value independently and can run once per dictionary value
+const DICTIONARY_VALUE_FUNCTIONS: &[&str] = &[
+    "length", "contains", "starts_with", "ends_with", "to_string", "to_number",
+    "md5", "sha256", "xxhash64", "hash_bucket", "base64_encode", "hex",
+];
+
+fn is_literal(expr: &JmesPathExpr) -> bool {
+    matches!(expr, JmesPathExpr::ConstInt(_) | JmesPathExpr::ConstInt64(_) | JmesPathExpr::ConstFloat(_) | JmesPathExpr::ConstBool(_)
+        | JmesPathExpr::ConstString(_) | JmesPathExpr::ConstNull)
+}
+
+/// Dictionary-encoded column referenced by a plain path argument
+fn dictionary_column(arg: &JmesPathExpr, rb: &RecordBatch) -> Option<ArrayRef> {
+    let JmesPathExpr::Path(parts) = arg else {
+        return None;
+    };
+    let column = rb.column_by_name(parts.first()?)?;
+    (parts.len() == 1 && matches!(column.data_type(), DataType::Dictionary(_, _))).then(|| column.clone())
+}
+
+/// Evaluate functions on dictionary-encoded path arguments: per-value functions with constant
+/// arguments run once on the dictionary values and are mapped back through the keys, all other
+/// functions see the decoded columns. None if no argument is dictionary-encoded.
+fn eval_dictionary_function<'a>(name: &str, args: &[JmesPathExpr], rb: &'a RecordBatch) -> Option<EvalResult<'a>> {
+    let dictionaries: Vec<ArrayRef> = args.iter().filter_map(|arg| dictionary_column(arg, rb)).collect();
+    if dictionaries.is_empty() {
+        return None;
+    }
+
+    if DICTIONARY_VALUE_FUNCTIONS.contains(&name) && args[1..].iter().all(is_literal) {
+        if let Some(column) = dictionary_column(&args[0], rb) {
+            let dict = column.as_any_dictionary();
+            let mut value_args = args.to_vec();
+            value_args[0] = JmesPathExpr::CurrentNode;
+            let call = JmesPathExpr::Func { name: name.to_string(), args: value_args };
+            let per_value = eval_with_value(&call, array_to_result(dict.values().clone()));
+            let per_value = result_to_array(&per_value)
+                .unwrap_or_else(|| panic!("{}() did not produce one value per dictionary entry", name));
+            let mapped = arrow::compute::take(per_value.as_ref(), dict.keys(), None)
+                .unwrap_or_else(|e| panic!("{}() failed to map dictionary values: {}", name, e));
+            return Some(array_to_result(mapped));
+        }
+    }
+
+    // Fallback: the dictionary columns passed as arguments are decoded, other columns are shared
+    let decoded_names: Vec<&String> = args.iter()
+        .filter_map(|arg| match arg {
+            JmesPathExpr::Path(parts) if dictionary_column(arg, rb).is_some() => parts.first(),
+            _ => None,
+        })
+        .collect();
+    let mut fields = Vec::with_capacity(rb.num_columns());
+    let mut columns = Vec::with_capacity(rb.num_columns());
+    for (field, column) in rb.schema().fields().iter().zip(rb.columns()) {
+        match column.data_type() {
+            DataType::Dictionary(_, value_type) if decoded_names.contains(&field.name()) => {
+                fields.push(field.as_ref().clone().with_data_type(value_type.as_ref().clone()));
+                columns.push(arrow::compute::cast(column, value_type).unwrap());
+            },
+            _ => {
+                fields.push(field.as_ref().clone());
+                columns.push(column.clone());
+            },
+        }
+    }
+    let schema = arrow::datatypes::Schema::new_with_metadata(fields, rb.schema().metadata().clone());
+    let decoded = RecordBatch::try_new(Arc::new(schema), columns).unwrap();
+    Some(into_owned(eval_function(name, args, &decoded)))
+}
+
+/// Decode a dictionary result into its value type (other results are returned unchanged)
+fn decode_dictionary(result: EvalResult) -> EvalResult {
+    match result {
+        EvalResult::DictionaryArray(arr) => {
+            let DataType::Dictionary(_, value_type) = arr.data_type() else { unreachable!() };
+            array_to_result(arrow::compute::cast(&arr, value_type).unwrap())
+        },
+        other => other,
+    }
+}
+
+fn flip_compare_op(op: &CompareOp) -> CompareOp {
+    match op {
+        CompareOp::Eq => CompareOp::Eq,
+        CompareOp::Ne => CompareOp::Ne,
+        CompareOp::Gt => CompareOp::Lt,
+        CompareOp::Lt => CompareOp::Gt,
+        CompareOp::Gte => CompareOp::Lte,
+        CompareOp::Lte => CompareOp::Gte,
+    }
+}
+
+/// Logic and filter operand under JMESPath truthiness: null, false and empty strings,
+/// lists and maps are false, everything else is true
+fn truthy(result: EvalResult) -> EvalResult {
+    match result {
+        EvalResult::BoolArray(mask) if mask.null_count() > 0 => {
+            EvalResult::BoolArray(arrow::compute::prep_null_mask_filter(&mask))
+        },
+        EvalResult::BoolArray(_) | EvalResult::BoolConst(_) => result,
+        EvalResult::NullConst => EvalResult::BoolConst(false),
+        other => match const_to_array(&other) {
+            Some(scalar) => EvalResult::BoolConst(truthy_rows(&scalar).value(0)),
+            None => {
+                let arr = result_to_array(&other)
+                    .unwrap_or_else(|| panic!("Cannot use {:?} as a condition", other));
+                EvalResult::BoolArray(truthy_rows(&arr))
+            },
+        },
+    }
+}
+
+/// Truthiness of every row of an array (null rows are false)
+fn truthy_rows(arr: &ArrayRef) -> BooleanArray {
+    use arrow::array::AsArray;
+    let len = arr.len();
+    let non_empty = match arr.data_type() {
+        DataType::Null => return BooleanArray::from(vec![false; len]),
+        DataType::Boolean => arr.as_boolean().clone(),
+        DataType::Dictionary(_, value_type) => return truthy_rows(&arrow::compute::cast(arr, value_type).unwrap()),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary
+        | DataType::List(_) | DataType::LargeList(_) => {
+            let lengths = arrow::compute::kernels::length::length(arr.as_ref()).unwrap();
+            let lengths = arrow::compute::cast(&lengths, &DataType::Int64).unwrap();
+            cmp::gt(&lengths, &Int64Array::new_scalar(0)).unwrap()
+        },
+        DataType::Map(_, _) => {
+            let offsets = arr.as_map().value_offsets();
+            let non_empty = offsets.windows(2).map(|w| w[1] > w[0]).collect::<Vec<_>>();
+            BooleanArray::new(non_empty.into(), arr.logical_nulls())
+        },
+        _ => BooleanArray::new(arrow::buffer::BooleanBuffer::new_set(len), arr.logical_nulls()),
+    };
+    if non_empty.null_count() > 0 {
+        arrow::compute::prep_null_mask_filter(&non_empty)
+    } else {
+        non_empty
+    }
+}
+
+/// Compare two evaluated operands (columns, constants or both)
+fn compare_results<'a>(op: &CompareOp, lhs_val: EvalResult, rhs_val: EvalResult) -> EvalResult<'a> {
+    // Null operands (missing fields, `null`): == and != test for null, ordering yields null
+    if let Some(result) = compare_null(op, &lhs_val, &rhs_val) {
+        return result;
+    }
+    
+    // Dictionary-encoded columns: compare each dictionary value once, other cases decode
+    if let Some(mask) = compare_dictionary(op, &lhs_val, &rhs_val) {
+        return EvalResult::BoolArray(mask);
+    }
+    let (lhs_val, rhs_val) = (decode_dictionary(lhs_val), decode_dictionary(rhs_val));
+    
+    // Numeric comparisons (any integer/float width, columns or constants)
+    if let (Some(l), Some(r)) = (numeric_operand(&lhs_val), numeric_operand(&rhs_val)) {
+        let target = comparison_type(&l, &r);
+        let all_scalar = l.is_scalar && r.is_scalar;
+        let [l, r] = [l, r].map(|operand| Operand {
+            array: arrow::compute::cast(&operand.array, &target)
+                .unwrap_or_else(|e| panic!("Cannot compare {:?} with {:?}: {}", operand.array.data_type(), target, e)),
+            is_scalar: operand.is_scalar,
+        });
+        let mask = compare_operands(op, &l, &r);
+        return if all_scalar { EvalResult::BoolConst(mask.value(0)) } else { EvalResult::BoolArray(mask) };
+    }
+    
+    // String comparisons
+    if let (EvalResult::StringArray(col), EvalResult::StringConst(s)) = (&lhs_val, &rhs_val) {
+        let scalar_value = arrow_array::Scalar::new(StringArray::from(vec![s.as_str()]));
+        return EvalResult::BoolArray(compare_datums(op, col.as_ref(), &scalar_value));
+    }
+    
+    // String column against string column
+    if let (EvalResult::StringArray(l), EvalResult::StringArray(r)) = (&lhs_val, &rhs_val) {
+        return EvalResult::BoolArray(compare_datums(op, l.as_ref(), r.as_ref()));
+    }
+    
+    // Binary comparisons (bytewise; strings compare by their UTF-8 bytes)
+    if matches!(lhs_val, EvalResult::BinaryArray(_)) || matches!(rhs_val, EvalResult::BinaryArray(_)) {
+        let [l, r] = [&lhs_val, &rhs_val].map(|side| {
+            let arr = result_to_array(side).map(|arr| (arr, false))
+                .or_else(|| const_to_array(side).map(|arr| (arr, true)))
+                .unwrap_or_else(|| panic!("Unsupported comparison types: {:?} {:?} {:?}", lhs_val, op, rhs_val));
+            let bytes = arrow::compute::cast(&arr.0, &DataType::Binary)
+                .unwrap_or_else(|e| panic!("Cannot compare {:?} with binary: {}", arr.0.data_type(), e));
+            (bytes, arr.1)
+        });
+        let mask = match (l.1, r.1) {
+            (false, true) => compare_datums(op, &l.0, &arrow_array::Scalar::new(r.0)),
+            (true, false) => compare_datums(op, &arrow_array::Scalar::new(l.0), &r.0),
+            _ => compare_datums(op, &l.0, &r.0),
+        };
+        return EvalResult::BoolArray(mask);
+    }
+    
+    // Boolean comparisons
+    if let (EvalResult::BoolArray(col), EvalResult::BoolConst(b)) = (&lhs_val, &rhs_val) {
+        let scalar_value = arrow_array::Scalar::new(BooleanArray::from(vec![*b]));
+        if !matches!(op, CompareOp::Eq | CompareOp::Ne) {
+            panic!("Only == and != supported for boolean comparisons");
+        }
+        return EvalResult::BoolArray(compare_datums(op, col, &scalar_value));
+    }
+
+    // Timestamp/Date comparisons (ISO-8601 strings, timestamp constants or other temporal columns);
+    // with the column on the right the operands swap sides and the operator flips
+    let temporal = match (&lhs_val, &rhs_val) {
+        (EvalResult::TimestampArray(col), other) => Some((op.clone(), col, other)),
+        (other, EvalResult::TimestampArray(col)) => Some((flip_compare_op(op), col, other)),
+        _ => None,
+    };
+    if let Some((op, col, other)) = temporal {
+        let op = &op;
+        let mask = match other {
+            EvalResult::StringConst(s) => {
+                let scalar_value = arrow_array::Scalar::new(timestamp_literal(s, col.data_type()));
+                compare_datums(op, col, &scalar_value)
+            },
+            EvalResult::TimestampConst(us) => {
+                let scalar_value = arrow_array::Scalar::new(timestamp_scalar(*us, col.data_type()));
+                compare_datums(op, col, &scalar_value)
+            },
+            EvalResult::TimestampArray(other) => {
+                let other = arrow::compute::cast(other, col.data_type())
+                    .unwrap_or_else(|e| panic!("Cannot compare {:?} with {:?}: {}", col.data_type(), other.data_type(), e));
+                compare_datums(op, col, &other)
+            },
+            _ => return mismatched_compare(op, &lhs_val, &rhs_val),
+        };
+        return EvalResult::BoolArray(mask);
+    }
+
+    if let (EvalResult::TimestampConst(a), EvalResult::TimestampConst(b)) = (&lhs_val, &rhs_val) {
+        let result = match op {
+            CompareOp::Eq => a == b,
+            CompareOp::Ne => a != b,
+            CompareOp::Gt => a > b,
+            CompareOp::Lt => a < b,
+            CompareOp::Gte => a >= b,
+            CompareOp::Lte => a <= b,
+        };
+        return EvalResult::BoolConst(result);
+    }
+
+    // Other operands of one type (booleans, strings with the literal first, ...)
+    let operand = |side: &EvalResult| result_to_array(side).map(|array| Operand { array, is_scalar: false })
+        .or_else(|| const_to_array(side).map(|array| Operand { array, is_scalar: true }));
+    if let (Some(l), Some(r)) = (operand(&lhs_val), operand(&rhs_val)) {
+        if l.array.data_type() == r.array.data_type() {
+            let mask = compare_operands(op, &l, &r);
+            return if l.is_scalar && r.is_scalar { EvalResult::BoolConst(mask.value(0)) } else { EvalResult::BoolArray(mask) };
+        }
+    }
+    
+    mismatched_compare(op, &lhs_val, &rhs_val)
+}
+
+/// Comparison of operands with different types (JMESPath: never equal, ordering is null)
+fn mismatched_compare<'a>(op: &CompareOp, lhs: &EvalResult, rhs: &EvalResult) -> EvalResult<'a> {
+    if matches!(lhs, EvalResult::RecordBatch(_)) || matches!(rhs, EvalResult::RecordBatch(_)) {
+        panic!("Unsupported comparison types: {:?} {:?} {:?}", lhs, op, rhs);
+    }
+    let value = match op {
+        CompareOp::Eq => Some(false),
+        CompareOp::Ne => Some(true),
+        _ => None,
+    };
+    match [lhs, rhs].iter().filter_map(|side| result_to_array(side)).map(|arr| arr.len()).max() {
+        Some(len) => EvalResult::BoolArray(BooleanArray::from(vec![value; len])),
+        None => value.map_or(EvalResult::NullConst, EvalResult::BoolConst),
+    }
+}
+
+/// Compare against an all-null operand (None if neither side is null-typed)
+fn compare_null<'a>(op: &CompareOp, lhs: &EvalResult, rhs: &EvalResult) -> Option<EvalResult<'a>> {
+    let is_null_operand = |r: &EvalResult| matches!(r, EvalResult::NullConst | EvalResult::NullArray(_));
+    if !is_null_operand(lhs) && !is_null_operand(rhs) {
+        return None;
+    }
+    let [(l, l_scalar), (r, r_scalar)] = [lhs, rhs].map(|side| {
+        result_to_array(side).map(|arr| (arr, false))
+            .or_else(|| const_to_array(side).map(|arr| (arr, true)))
+            .unwrap_or_else(|| panic!("Unsupported comparison types: {:?} {:?} {:?}", lhs, op, rhs))
+    });
+    let null_at = |arr: &ArrayRef, scalar: bool, i: usize| {
+        arr.data_type() == &DataType::Null || arr.is_null(if scalar { 0 } else { i })
+    };
+    let len = if l_scalar { r.len() } else { l.len() };
+    // One side is entirely null, so equality is a null test on the other side
+    let mask: BooleanArray = (0..len)
+        .map(|i| {
+            let same = null_at(&l, l_scalar, i) == null_at(&r, r_scalar, i);
+            match op {
+                CompareOp::Eq => Some(same),
+                CompareOp::Ne => Some(!same),
+                _ => None,
+            }
+        })
+        .collect();
+    Some(if l_scalar && r_scalar { scalar_from_array(&mask, 0) } else { EvalResult::BoolArray(mask) })
+}
+
+/// Compare a dictionary column with a constant: the comparison runs on the dictionary values only
+/// and the per-value results are mapped through the keys (None for other operand combinations)
+fn compare_dictionary(op: &CompareOp, lhs: &EvalResult, rhs: &EvalResult) -> Option<BooleanArray> {
+    match (lhs, rhs) {
+        (EvalResult::DictionaryArray(arr), other) => {
+            let constant = scalar_from_array(const_to_array(other)?.as_ref(), 0);
+            let dict = arr.as_any_dictionary();
+            // Same semantics as a plain column of the value type (`status == `5`` never matches "5")
+            let per_value = match compare_results(op, array_to_result(dict.values().clone()), constant) {
+                EvalResult::BoolArray(mask) => mask,
+                result => panic!("Unexpected dictionary comparison result: {:?}", result),
+            };
+            let mask = arrow::compute::take(&per_value, dict.keys(), None).unwrap();
+            // Null rows compared with a non-null constant: unequal (ordering stays null)
+            let mask: BooleanArray = match op {
+                CompareOp::Eq | CompareOp::Ne => mask.as_boolean().iter()
+                    .map(|v| Some(v.unwrap_or(matches!(op, CompareOp::Ne))))
+                    .collect(),
+                _ => mask.as_boolean().clone(),
+            };
+            Some(mask)
+        },
+        (_, EvalResult::DictionaryArray(_)) => compare_dictionary(&flip_compare_op(op), rhs, lhs),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// NULL PROPAGATION
+// ============================================================================
+
+/// True for arguments without any value (missing fields and null literals)
+fn is_null_input(arg: &EvalResult) -> bool {
+    matches!(arg, EvalResult::NullArray(_) | EvalResult::NullConst)
+}
+
+/// Null result of a row function if any argument is all-null:
+/// a NullArray with one row per input row, or NullConst if all arguments are constants
+fn null_propagation<'a>(args: &[&EvalResult]) -> Option<EvalResult<'a>> {
+    if !args.iter().any(|arg| is_null_input(arg)) {
+        return None;
+    }
+    let len = args.iter().filter_map(|arg| result_to_array(arg)).map(|arr| arr.len()).max();
+    Some(match len {
+        Some(len) => EvalResult::NullArray(Arc::new(arrow::array::NullArray::new(len))),
+        None => EvalResult::NullConst,
+    })
+}
@@ -1,11 +1,11 @@
 use crate::jmespath_ast::*;
 use nom::{
     IResult, 
-    bytes::complete::{tag, take_while1, escaped},
+    bytes::complete::{tag, take_while1, escaped, is_not},
     character::complete::{digit1, alpha1, multispace0, char, one_of},
-    combinator::{map_res, map, opt, recognize},
+    combinator::{map_res, map, opt, peek, recognize},
     branch::alt,
-    multi::separated_list1,
+    multi::{separated_list1, many0},
     sequence::{preceded, delimited, tuple},
 };
 
@@ -42,9 +42,9 @@ fn parse_bool(input: &str) -> IResult<&str, JmesPathExpr> {
     ))(input)
 }
 
-/// Parse String: "hello", "world\"test"
-fn parse_string(input: &str) -> IResult<&str, JmesPathExpr> {
-    let (rest, s) = delimited(
+/// Double-quoted text: "hello", "world\"test"
+fn parse_double_quoted(input: &str) -> IResult<&str, &str> {
+    delimited(
         char('"'),
         escaped(
             take_while1(|c| c != '"' && c != '\\'),
@@ -52,7 +52,12 @@ fn parse_string(input: &str) -> IResult<&str, JmesPathExpr> {
             one_of("\"\\nrt")
         ),
         char('"')
-    )(input)?;
+    )(input)
+}
+
+/// Parse String: "hello", "world\"test"
+fn parse_string(input: &str) -> IResult<&str, JmesPathExpr> {
+    let (rest, s) = parse_double_quoted(input)?;
     Ok((rest, JmesPathExpr::ConstString(s.to_string())))
 }
 
@@ -70,6 +75,36 @@ fn parse_string_single(input: &str) -> IResult<&str, JmesPathExpr> {
     Ok((rest, JmesPathExpr::ConstString(s.to_string())))
 }
 
+/// Convert a parsed JSON value into constant expressions (arrays become multi-select lists;
+/// objects have no constant representation and are rejected)
+fn json_literal_to_expr(value: serde_json::Value) -> Result<JmesPathExpr, String> {
+    use serde_json::Value;
+    Ok(match value {
+        Value::Null => JmesPathExpr::ConstNull,
+        Value::Bool(b) => JmesPathExpr::ConstBool(b),
+        Value::Number(n) => match n.as_i64() {
+            Some(v) => i32::try_from(v).map_or(JmesPathExpr::ConstInt64(v), JmesPathExpr::ConstInt),
+            None => JmesPathExpr::ConstFloat(n.as_f64().unwrap()),
+        },
+        Value::String(s) => JmesPathExpr::ConstString(s),
+        Value::Array(items) => JmesPathExpr::MultiSelectList(
+            items.into_iter().map(json_literal_to_expr).collect::<Result<_, _>>()?
+        ),
+        Value::Object(_) => return Err("object literals are not supported".to_string()),
+    })
+}
+
+/// Parse JSON literal in backticks: `1`, `"text"`, `null`, `[1, 2]`
+fn parse_json_literal(input: &str) -> IResult<&str, JmesPathExpr> {
+    delimited(
+        char('`'),
+        map_res(is_not("`"), |text: &str| {
+            serde_json::from_str::<serde_json::Value>(text).map_err(|e| e.to_string()).and_then(json_literal_to_expr)
+        }),
+        char('`')
+    )(input)
+}
+
 // ============================================================================
 // PATHS
 // ============================================================================
@@ -85,9 +120,20 @@ fn parse_identifier(input: &str) -> IResult<&str, String> {
     Ok((rest, result))
 }
 
-/// Parse path: a.b.c or just a
+/// Parse path segment after a dot: identifier, quoted identifier ("k8s.io/name") or wildcard (*)
+fn parse_path_segment(input: &str) -> IResult<&str, String> {
+    alt((
+        parse_identifier,
+        map(parse_double_quoted, |s: &str| s.to_string()),
+        map(tag("*"), |s: &str| s.to_string()),
+    ))(input)
+}
+
+/// Parse path: a.b.c, a."x.y", a.* or just a
 fn parse_path(input: &str) -> IResult<&str, JmesPathExpr> {
-    let (rest, parts) = separated_list1(tag("."), parse_identifier)(input)?;
+    let (rest, first) = parse_identifier(input)?;
+    let (rest, more) = many0(preceded(char('.'), parse_path_segment))(rest)?;
+    let parts = std::iter::once(first).chain(more).collect();
     Ok((rest, JmesPathExpr::Path(parts)))
 }
 
@@ -135,8 +181,11 @@ fn parse_index(input: &str) -> IResult<&str, i32> {
     Ok((rest, value))
 }
 
+/// Slice bounds: (start, stop, step)
+type SliceBounds = (Option<i32>, Option<i32>, Option<i32>);
+
 /// Parse array slice: [0:5], [::2], [1:10:2]
-fn parse_slice(input: &str) -> IResult<&str, (Option<i32>, Option<i32>, Option<i32>)> {
+fn parse_slice(input: &str) -> IResult<&str, SliceBounds> {
     let (rest, _) = char('[')(input)?;
     let (rest, _) = multispace0(rest)?;
     
@@ -265,9 +314,31 @@ fn parse_expr_ref(input: &str) -> IResult<&str, JmesPathExpr> {
     Ok((rest, JmesPathExpr::ExprRef(Box::new(expr))))
 }
 
+/// Leading bracket operation (`[]`, `[*]`, `[0:2]`, `[0]`, `[?...]`) applies to the current node;
+/// consumes nothing so the array-operation loop in parse_primary_until picks the bracket up
+fn parse_leading_bracket(input: &str) -> IResult<&str, JmesPathExpr> {
+    let (_, _) = peek(alt((
+        map(parse_flatten_bracket, |_| ()),
+        map(parse_projection_wildcard, |_| ()),
+        map(parse_slice, |_| ()),
+        map(parse_index, |_| ()),
+        map(tag("[?"), |_| ()),
+    )))(input)?;
+    Ok((input, JmesPathExpr::CurrentNode))
+}
+
 /// Parse primary expression (literals, paths, functions, or parenthesized expressions)
 /// Also handles array operations: [0], [0:5], [*].field
 fn parse_primary(input: &str) -> IResult<&str, JmesPathExpr> {
+    parse_primary_until(input, false)
+}
+
+/// Right-hand side of a projection: a flatten `[]` ends the projection and applies to its result
+fn parse_projected(input: &str) -> IResult<&str, JmesPathExpr> {
+    parse_primary_until(input, true)
+}
+
+fn parse_primary_until(input: &str, stop_at_flatten: bool) -> IResult<&str, JmesPathExpr> {
     let (rest, _) = multispace0(input)?;
     
     // First parse the base expression
@@ -279,7 +350,10 @@ fn parse_primary(input: &str) -> IResult<&str, JmesPathExpr> {
         parse_int,
         parse_string,
         parse_string_single,
+        parse_json_literal,  // `1`, `"text"`, `null`
         parse_multi_select_hash,  // {key: expr}
+        parse_leading_bracket,    // [0], [*], [?...] - must come before multi-select list
+        parse_multi_select_list,  // [expr1, expr2]
         delimited(
             char('('),
             preceded(multispace0, parse_pipe),
@@ -296,7 +370,19 @@ fn parse_primary(input: &str) -> IResult<&str, JmesPathExpr> {
         
         // Check for flatten: []
         if let Ok((rest2, _)) = parse_flatten_bracket(new_rest) {
+            if stop_at_flatten {
+                break;
+            }
             expr = JmesPathExpr::Flatten(Box::new(expr));
+            
+            // Flatten is a projection: a following path applies to every element
+            let (rest3, _) = multispace0(rest2)?;
+            if let Ok((rest4, _)) = char::<_, nom::error::Error<&str>>('.')(rest3) {
+                let (rest5, field_expr) = parse_projected(rest4)?;
+                expr = JmesPathExpr::Projection(Box::new(expr), Box::new(field_expr));
+                current_rest = rest5;
+                continue;
+            }
             current_rest = rest2;
             continue;
         }
@@ -307,7 +393,7 @@ fn parse_primary(input: &str) -> IResult<&str, JmesPathExpr> {
             
             // Check if there's a path after the projection
             if let Ok((rest4, _)) = char::<_, nom::error::Error<&str>>('.')(rest3) {
-                let (rest5, field_expr) = parse_primary(rest4)?;
+                let (rest5, field_expr) = parse_projected(rest4)?;
                 expr = JmesPathExpr::Projection(Box::new(expr), Box::new(field_expr));
                 current_rest = rest5;
                 continue;
@@ -1,7 +1,10 @@
+use arrow::array::Array;
 use arrow::json::ReaderBuilder;
 use arrow::record_batch::RecordBatch;
 use serde_json::Value;
 use std::io::Cursor;
+use crate::arrow_utils::dictionary_key;
+use crate::eval_options::{eval_options, with_eval_options, DecimalJson, EvalOptions, NonFiniteJson};
 
 /// JSON → Arrow RecordBatch (allgemein)
 /// Accepts either an array of objects or a single object
@@ -22,20 +25,18 @@ pub fn json_to_arrow(json_data: &Value) -> RecordBatch {
 
     // Schema inferieren und dann den Reader erstellen
     let (schema, _) = arrow::json::reader::infer_json_schema(cursor.clone(), None).unwrap();
-    let mut reader = ReaderBuilder::new(std::sync::Arc::new(schema))
+    let schema = std::sync::Arc::new(schema);
+    let reader = ReaderBuilder::new(schema.clone())
         .build(cursor)
         .unwrap();
 
-    // Erstes RecordBatch zurückgeben
-    reader.next().unwrap().unwrap()
+    // Alle RecordBatches zu einem zusammenfassen (der Reader liefert max. 1024 Zeilen pro Batch)
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    arrow::compute::concat_batches(&schema, &batches).unwrap()
 }
 
 /// Arrow RecordBatch → JSON (Array von Objekten)
 pub fn arrow_to_json(record_batch: &RecordBatch) -> Value {
-    use arrow::array::*;
-    use arrow::datatypes::DataType;
-    use serde_json::json;
-    
     let schema = record_batch.schema();
     let num_rows = record_batch.num_rows();
     let mut rows = Vec::new();
@@ -49,64 +50,7 @@ pub fn arrow_to_json(record_batch: &RecordBatch) -> Value {
             let column = record_batch.column(col_idx);
             let field_name = field.name().clone();
             
-            // Wert basierend auf Datentyp extrahieren
-            let value = if column.is_null(row_idx) {
-                Value::Null
-            } else {
-                match field.data_type() {
-                    DataType::Int32 => {
-                        let arr = column.as_any().downcast_ref::<Int32Array>().unwrap();
-                        json!(arr.value(row_idx))
-                    },
-                    DataType::Int64 => {
-                        let arr = column.as_any().downcast_ref::<Int64Array>().unwrap();
-                        json!(arr.value(row_idx))
-                    },
-                    DataType::Float32 => {
-                        let arr = column.as_any().downcast_ref::<Float32Array>().unwrap();
-                        json!(arr.value(row_idx))
-                    },
-                    DataType::Float64 => {
-                        let arr = column.as_any().downcast_ref::<Float64Array>().unwrap();
-                        json!(arr.value(row_idx))
-                    },
-                    DataType::Utf8 => {
-                        let arr = column.as_any().downcast_ref::<StringArray>().unwrap();
-                        json!(arr.value(row_idx))
-                    },
-                    DataType::Boolean => {
-                        let arr = column.as_any().downcast_ref::<BooleanArray>().unwrap();
-                        json!(arr.value(row_idx))
-                    },
-                    DataType::List(_) => {
-                        let arr = column.as_any().downcast_ref::<ListArray>().unwrap();
-                        let list = arr.value(row_idx);
-                        
-                        // Liste zu JSON-Array konvertieren
-                        let mut json_array = Vec::new();
-                        if let Some(int_arr) = list.as_any().downcast_ref::<Int64Array>() {
-                            for i in 0..int_arr.len() {
-                                if !int_arr.is_null(i) {
-                                    json_array.push(json!(int_arr.value(i)));
-                                }
-                            }
-                        } else if let Some(str_arr) = list.as_any().downcast_ref::<StringArray>() {
-                            for i in 0..str_arr.len() {
-                                if !str_arr.is_null(i) {
-                                    json_array.push(json!(str_arr.value(i)));
-                                }
-                            }
-                        }
-                        json!(json_array)
-                    },
-                    _ => {
-                        // Für nicht unterstützte Typen null zurückgeben
-                        Value::Null
-                    }
-                }
-            };
-            
-            row_obj.insert(field_name, value);
+            row_obj.insert(field_name, array_value_to_json(column.as_ref(), row_idx));
         }
         
         rows.push(Value::Object(row_obj));
@@ -115,6 +59,155 @@ pub fn arrow_to_json(record_batch: &RecordBatch) -> Value {
     Value::Array(rows)
 }
 
+/// Arrow RecordBatch → JSON mit Optionen (z.B. NaN/Infinity als Strings, Dezimalwerte als Zahlen)
+pub fn arrow_to_json_with_options(record_batch: &RecordBatch, options: &EvalOptions) -> Value {
+    with_eval_options(options, || arrow_to_json(record_batch))
+}
+
+/// Float → JSON; NaN/±Infinity je nach `FloatPolicy` als null oder String
+fn float_to_json(value: f64) -> Value {
+    if value.is_finite() {
+        return serde_json::json!(value);
+    }
+    match eval_options().float_policy.non_finite_json {
+        NonFiniteJson::Null => Value::Null,
+        NonFiniteJson::String if value.is_nan() => Value::from("NaN"),
+        NonFiniteJson::String if value > 0.0 => Value::from("Infinity"),
+        NonFiniteJson::String => Value::from("-Infinity"),
+    }
+}
+
+/// Einzelner Arrow-Wert → JSON (Listen und Structs rekursiv)
+pub fn array_value_to_json(column: &dyn Array, row_idx: usize) -> Value {
+    use arrow::array::*;
+    use arrow::datatypes::*;
+    use serde_json::json;
+
+    if column.is_null(row_idx) {
+        return Value::Null;
+    }
+
+    // Wert basierend auf Datentyp extrahieren
+    match column.data_type() {
+        DataType::Int32 => {
+            let arr = column.as_any().downcast_ref::<Int32Array>().unwrap();
+            json!(arr.value(row_idx))
+        },
+        DataType::Int64 => {
+            let arr = column.as_any().downcast_ref::<Int64Array>().unwrap();
+            json!(arr.value(row_idx))
+        },
+        DataType::Float32 => {
+            let arr = column.as_any().downcast_ref::<Float32Array>().unwrap();
+            let value = arr.value(row_idx);
+            if value.is_finite() { json!(value) } else { float_to_json(value as f64) }
+        },
+        DataType::Float64 => {
+            let arr = column.as_any().downcast_ref::<Float64Array>().unwrap();
+            float_to_json(arr.value(row_idx))
+        },
+        DataType::Int8 => json!(column.as_primitive::<Int8Type>().value(row_idx)),
+        DataType::Int16 => json!(column.as_primitive::<Int16Type>().value(row_idx)),
+        DataType::UInt8 => json!(column.as_primitive::<UInt8Type>().value(row_idx)),
+        DataType::UInt16 => json!(column.as_primitive::<UInt16Type>().value(row_idx)),
+        DataType::UInt32 => json!(column.as_primitive::<UInt32Type>().value(row_idx)),
+        DataType::UInt64 => json!(column.as_primitive::<UInt64Type>().value(row_idx)),
+        DataType::Float16 => float_to_json(column.as_primitive::<Float16Type>().value(row_idx).to_f64()),
+        DataType::Utf8 => {
+            let arr = column.as_any().downcast_ref::<StringArray>().unwrap();
+            json!(arr.value(row_idx))
+        },
+        DataType::LargeUtf8 => json!(column.as_string::<i64>().value(row_idx)),
+        DataType::Utf8View => json!(column.as_string_view().value(row_idx)),
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            // Binärdaten als Base64-String
+            use base64::Engine;
+            let bytes = match column.data_type() {
+                DataType::Binary => column.as_binary::<i32>().value(row_idx),
+                DataType::LargeBinary => column.as_binary::<i64>().value(row_idx),
+                _ => column.as_binary_view().value(row_idx),
+            };
+            json!(base64::engine::general_purpose::STANDARD.encode(bytes))
+        },
+        DataType::Boolean => {
+            let arr = column.as_any().downcast_ref::<BooleanArray>().unwrap();
+            json!(arr.value(row_idx))
+        },
+        DataType::List(_) => {
+            let arr = column.as_any().downcast_ref::<ListArray>().unwrap();
+            let list = arr.value(row_idx);
+
+            // Liste zu JSON-Array konvertieren
+            let json_array: Vec<Value> = (0..list.len())
+                .map(|i| array_value_to_json(list.as_ref(), i))
+                .collect();
+            json!(json_array)
+        },
+        DataType::LargeList(_) | DataType::FixedSizeList(_, _) | DataType::ListView(_) | DataType::LargeListView(_) => {
+            // Übrige Listen-Layouts wie List behandeln
+            let list = match column.data_type() {
+                DataType::LargeList(_) => column.as_list::<i64>().value(row_idx),
+                DataType::FixedSizeList(_, _) => column.as_fixed_size_list().value(row_idx),
+                DataType::ListView(_) => column.as_any().downcast_ref::<ListViewArray>().unwrap().value(row_idx),
+                _ => column.as_any().downcast_ref::<LargeListViewArray>().unwrap().value(row_idx),
+            };
+            let json_array: Vec<Value> = (0..list.len())
+                .map(|i| array_value_to_json(list.as_ref(), i))
+                .collect();
+            json!(json_array)
+        },
+        DataType::Struct(fields) => {
+            let arr = column.as_any().downcast_ref::<StructArray>().unwrap();
+
+            // Struct zu JSON-Objekt konvertieren
+            let mut obj = serde_json::Map::new();
+            for (field, child) in fields.iter().zip(arr.columns()) {
+                obj.insert(field.name().clone(), array_value_to_json(child.as_ref(), row_idx));
+            }
+            Value::Object(obj)
+        },
+        DataType::Map(_, _) => {
+            // Map zu JSON-Objekt konvertieren (Schlüssel als String)
+            let arr = column.as_map();
+            let entries = arr.value(row_idx);
+            let mut obj = serde_json::Map::new();
+            for i in 0..entries.len() {
+                let key = match array_value_to_json(entries.column(0).as_ref(), i) {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                obj.insert(key, array_value_to_json(entries.column(1).as_ref(), i));
+            }
+            Value::Object(obj)
+        },
+        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            // Dezimalwerte je nach `DecimalJson` für die ganze Spalte als String (alle Stellen) oder als Zahl
+            use arrow::util::display::{ArrayFormatter, FormatOptions};
+            let formatter = ArrayFormatter::try_new(column, &FormatOptions::default()).unwrap();
+            let text = formatter.value(row_idx).to_string();
+            match eval_options().decimal_json {
+                DecimalJson::String => Value::String(text),
+                DecimalJson::Number => float_to_json(text.parse::<f64>().unwrap()),
+            }
+        },
+        DataType::Dictionary(_, _) => {
+            // Wert über den Schlüssel im Dictionary nachschlagen
+            let dict = column.as_any_dictionary();
+            array_value_to_json(dict.values().as_ref(), dictionary_key(dict, row_idx))
+        },
+        DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => {
+            // ISO-8601 Darstellung (z.B. 2024-03-01T12:30:00)
+            use arrow::util::display::{ArrayFormatter, FormatOptions};
+            let formatter = ArrayFormatter::try_new(column, &FormatOptions::default()).unwrap();
+            json!(formatter.value(row_idx).to_string())
+        },
+        _ => {
+            // Für nicht unterstützte Typen null zurückgeben
+            Value::Null
+        }
+    }
+}
+
 /// Arrow RecordBatch → Pretty JSON String
 pub fn arrow_to_json_string(record_batch: &RecordBatch) -> String {
     let json = arrow_to_json(record_batch);
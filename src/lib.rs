@@ -3,8 +3,10 @@ pub mod arrow_utils;
 pub mod jmespath_ast;
 pub mod jmespath_parser;
 pub mod jmespath_eval;
+pub mod eval_options;
 
-pub use json_to_arrow::{json_to_arrow, arrow_to_json, arrow_to_json_string, arrow_to_json_string_compact};
+pub use json_to_arrow::{json_to_arrow, arrow_to_json, arrow_to_json_with_options, arrow_to_json_string, arrow_to_json_string_compact};
 pub use jmespath_parser::parse_jmespath;
-pub use jmespath_eval::{eval_jmespath, EvalResult};
+pub use jmespath_eval::{eval_jmespath, eval_jmespath_with_options, EvalResult};
+pub use eval_options::{EvalOptions, ConversionErrorMode, MissingFieldMode, FloatPolicy, FloatOrdering, NanAggregation, NonFiniteJson, DecimalJson};
 pub use jmespath_ast::*;
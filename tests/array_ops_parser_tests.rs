@@ -1,3 +1,4 @@
+#![allow(clippy::items_after_test_module)]
 use arrow_jmespath::parse_jmespath;
 
 #[cfg(test)]
@@ -0,0 +1,130 @@
+// Tests for Index and Slice on RecordBatch results and out-of-range semantics
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::array::{Array, Int64Array, StringArray};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn people_batch() -> RecordBatch {
+    let data = json!([
+        {"name": "carl", "age": 41, "active": true},
+        {"name": "anna", "age": 29, "active": false},
+        {"name": "ben", "age": 35, "active": true},
+        {"name": "dora", "age": null, "active": true}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_index_on_batch_returns_single_row_object() {
+    let rb = people_batch();
+
+    if let EvalResult::StructConst(row) = eval("sort_by(@, &name)[0]", &rb) {
+        assert_eq!(row.len(), 1);
+        let names = row.column_by_name("name").unwrap().as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(names.value(0), "anna");
+    } else {
+        panic!("Expected StructConst");
+    }
+
+    if let EvalResult::StructConst(row) = eval("people[?active][-1]", &rb) {
+        let ages = row.column_by_name("age").unwrap();
+        assert!(ages.is_null(0));
+    } else {
+        panic!("Expected StructConst");
+    }
+
+    assert!(matches!(eval("type(@[1])", &rb), EvalResult::StringConst(s) if s == "object"));
+}
+
+#[test]
+fn test_index_out_of_range_is_null() {
+    let rb = people_batch();
+
+    assert!(matches!(eval("@[10]", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("@[-5]", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("age[7]", &rb), EvalResult::NullConst));
+    // Null elements are null instead of an error
+    assert!(matches!(eval("age[3]", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("name[-1]", &rb), EvalResult::StringConst(s) if s == "dora"));
+}
+
+#[test]
+fn test_slice_on_batch_is_zero_copy() {
+    let rb = people_batch();
+
+    if let EvalResult::RecordBatch(sliced) = eval("people[?active][0:2]", &rb) {
+        assert_eq!(arrow_to_json(&sliced), json!([
+            {"name": "carl", "age": 41, "active": true},
+            {"name": "ben", "age": 35, "active": true}
+        ]));
+    } else {
+        panic!("Expected RecordBatch");
+    }
+
+    if let EvalResult::RecordBatch(sliced) = eval("@[1:]", &rb) {
+        assert_eq!(sliced.num_rows(), 3);
+        assert_eq!(sliced.column(0).to_data().offset(), 1);
+        assert_eq!(sliced.column(0).to_data().buffers()[0].as_ptr(), rb.column(0).to_data().buffers()[0].as_ptr());
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_stepped_and_reversed_slices_on_batch() {
+    let rb = people_batch();
+
+    if let EvalResult::RecordBatch(sliced) = eval("@[::-1]", &rb) {
+        let rows = arrow_to_json(&sliced);
+        assert_eq!(rows[0]["name"], "dora");
+        assert_eq!(rows[3]["name"], "carl");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+
+    if let EvalResult::RecordBatch(sliced) = eval("@[::2]", &rb) {
+        assert_eq!(arrow_to_json(&sliced), json!([
+            {"name": "carl", "age": 41, "active": true},
+            {"name": "ben", "age": 35, "active": true}
+        ]));
+    } else {
+        panic!("Expected RecordBatch");
+    }
+
+    // Out-of-range bounds are clamped
+    if let EvalResult::RecordBatch(sliced) = eval("@[10:20]", &rb) {
+        assert_eq!(sliced.num_rows(), 0);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_slice_on_columns_keeps_nulls() {
+    let rb = people_batch();
+
+    if let EvalResult::Int64Array(ages) = eval("age[2:]", &rb) {
+        assert_eq!(ages.len(), 2);
+        assert_eq!(ages.value(0), 35);
+        assert!(ages.is_null(1));
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::BoolArray(flags) = eval("active[1:3]", &rb) {
+        assert_eq!(flags.len(), 2);
+        assert!(!flags.value(0));
+    } else {
+        panic!("Expected BoolArray");
+    }
+
+    if let EvalResult::Int64Array(ages) = eval("age[::-2]", &rb) {
+        let expected = Int64Array::from(vec![None, Some(29)]);
+        assert_eq!(ages.as_ref(), &expected);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
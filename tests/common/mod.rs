@@ -0,0 +1,48 @@
+// Helpers shared by the integration tests (each test crate uses a subset)
+#![allow(dead_code)]
+
+use arrow_jmespath::{parse_jmespath, eval_jmespath, arrow_to_json, EvalResult};
+use arrow::record_batch::RecordBatch;
+use serde_json::Value;
+
+pub fn eval<'a>(query: &str, rb: &'a RecordBatch) -> EvalResult<'a> {
+    let (_, expr) = parse_jmespath(query).unwrap();
+    eval_jmespath(&expr, rb)
+}
+
+/// Evaluate a batch-producing query and render its rows as JSON
+pub fn eval_rows(query: &str, rb: &RecordBatch) -> Vec<Value> {
+    if let EvalResult::RecordBatch(result) = eval(query, rb) {
+        arrow_to_json(&result).as_array().unwrap().clone()
+    } else {
+        panic!("Expected RecordBatch for {}", query);
+    }
+}
+
+pub fn bools(result: EvalResult) -> Vec<Option<bool>> {
+    match result {
+        EvalResult::BoolArray(arr) => arr.iter().collect(),
+        other => panic!("Expected BoolArray, got {:?}", other),
+    }
+}
+
+pub fn ints(result: EvalResult) -> Vec<Option<i32>> {
+    match result {
+        EvalResult::Int32Array(arr) => arr.iter().collect(),
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+}
+
+pub fn strings(result: EvalResult) -> Vec<Option<String>> {
+    match result {
+        EvalResult::StringArray(arr) => arr.iter().map(|s| s.map(str::to_string)).collect(),
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+}
+
+pub fn as_float(result: EvalResult) -> f64 {
+    match result {
+        EvalResult::FloatConst(v) => v,
+        other => panic!("Expected FloatConst, got {:?}", other),
+    }
+}
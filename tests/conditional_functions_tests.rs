@@ -0,0 +1,163 @@
+// Tests for conditional and null-handling functions: if, case, coalesce, if_null, nullif, is_null
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn people_batch() -> RecordBatch {
+    let data = json!([
+        {"name": "Alice", "nickname": "Ali", "age": 34, "score": 95, "status": "active"},
+        {"name": "Bob", "nickname": null, "age": 17, "score": 80, "status": "unknown"},
+        {"name": null, "nickname": null, "age": 52, "score": null, "status": "inactive"}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_if_with_constants() {
+    let rb = people_batch();
+
+    if let EvalResult::StringArray(arr) = eval("if(age >= 18, 'adult', 'minor')", &rb) {
+        assert_eq!(arr.value(0), "adult");
+        assert_eq!(arr.value(1), "minor");
+        assert_eq!(arr.value(2), "adult");
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_if_mixes_column_and_constant() {
+    let rb = people_batch();
+
+    // Int64 column and Int32 constant are unified to Int64
+    if let EvalResult::Int64Array(arr) = eval("if(age > 40, 0, age)", &rb) {
+        assert_eq!(arr.value(0), 34);
+        assert_eq!(arr.value(2), 0);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+fn test_if_with_constant_condition() {
+    let rb = people_batch();
+    assert!(matches!(eval("if(true, 'yes', 'no')", &rb), EvalResult::StringConst(ref s) if s == "yes"));
+}
+
+#[test]
+fn test_case_with_default() {
+    let rb = people_batch();
+
+    if let EvalResult::RecordBatch(result) = eval("{grade: case(score >= 90, 'A', score >= 75, 'B', 'C')}", &rb) {
+        let json_result = arrow_to_json(&result);
+        assert_eq!(json_result[0]["grade"], "A");
+        assert_eq!(json_result[1]["grade"], "B");
+        // null score: no condition matches, default applies
+        assert_eq!(json_result[2]["grade"], "C");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_case_without_default_yields_null() {
+    let rb = people_batch();
+
+    if let EvalResult::StringArray(arr) = eval("case(age < 18, 'minor')", &rb) {
+        assert!(arr.is_null(0));
+        assert_eq!(arr.value(1), "minor");
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_coalesce_multiple_columns() {
+    let rb = people_batch();
+
+    if let EvalResult::StringArray(arr) = eval("coalesce(nickname, name, 'anonymous')", &rb) {
+        assert_eq!(arr.value(0), "Ali");
+        assert_eq!(arr.value(1), "Bob");
+        assert_eq!(arr.value(2), "anonymous");
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_if_null_replaces_nulls() {
+    let rb = people_batch();
+
+    if let EvalResult::Int64Array(arr) = eval("if_null(score, 0)", &rb) {
+        assert_eq!(arr.null_count(), 0);
+        assert_eq!(arr.value(0), 95);
+        assert_eq!(arr.value(2), 0);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+fn test_nullif_column_and_constant() {
+    let rb = people_batch();
+
+    if let EvalResult::StringArray(arr) = eval("nullif(status, 'unknown')", &rb) {
+        assert_eq!(arr.value(0), "active");
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), "inactive");
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_is_null_per_row() {
+    let rb = people_batch();
+
+    if let EvalResult::BoolArray(mask) = eval("is_null(nickname)", &rb) {
+        assert!(!mask.value(0));
+        assert!(mask.value(1));
+        assert!(mask.value(2));
+    } else {
+        panic!("Expected BoolArray");
+    }
+
+    // Rows are kept, only the column is cleaned
+    if let EvalResult::RecordBatch(result) = eval("{name: coalesce(name, 'n/a'), missing: is_null(name)}", &rb) {
+        assert_eq!(result.num_rows(), 3);
+        let json_result = arrow_to_json(&result);
+        assert_eq!(json_result[2]["name"], "n/a");
+        assert_eq!(json_result[2]["missing"], true);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_is_null_of_constants() {
+    let rb = people_batch();
+
+    assert!(matches!(eval("is_null(`null`)", &rb), EvalResult::BoolConst(true)));
+    assert!(matches!(eval("is_null('x')", &rb), EvalResult::BoolConst(false)));
+    assert!(matches!(eval("is_null(`0`)", &rb), EvalResult::BoolConst(false)));
+}
+
+#[test]
+fn test_conditions_follow_truthiness() {
+    let rb = people_batch();
+
+    // Non-empty strings are true, null and empty values are false
+    assert_eq!(strings(eval("if(nickname, 'has nick', 'none')", &rb)),
+        vec![Some("has nick".to_string()), Some("none".to_string()), Some("none".to_string())]);
+    assert!(matches!(eval("if(`\"\"`, 'yes', 'no')", &rb), EvalResult::StringConst(ref s) if s == "no"));
+    assert!(matches!(eval("if(`null`, 'yes', 'no')", &rb), EvalResult::StringConst(ref s) if s == "no"));
+    assert_eq!(strings(eval("case(name, name, 'anonymous')", &rb)),
+        vec![Some("Alice".to_string()), Some("Bob".to_string()), Some("anonymous".to_string())]);
+    assert_eq!(strings(eval("case(score > `85`, 'top', 'rest')", &rb)),
+        vec![Some("top".to_string()), Some("rest".to_string()), Some("rest".to_string())]);
+}
@@ -0,0 +1,171 @@
+// Tests for conversion functions: to_string, to_number, to_boolean, to_timestamp, cast
+use arrow_jmespath::{parse_jmespath, eval_jmespath_with_options, json_to_arrow, arrow_to_json};
+use arrow_jmespath::{EvalResult, EvalOptions, ConversionErrorMode};
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn strict() -> EvalOptions {
+    EvalOptions { on_conversion_error: ConversionErrorMode::Error, ..Default::default() }
+}
+
+fn orders_batch() -> RecordBatch {
+    let data = json!([
+        {"id": 1, "amount": "19.99", "flag": "yes", "qty": 3, "price": 2.5, "created": "2024-01-15T08:30:00Z"},
+        {"id": 2, "amount": "n/a", "flag": "false", "qty": null, "price": 7.75, "created": "not a date"},
+        {"id": 3, "amount": null, "flag": null, "qty": 0, "price": null, "created": null}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_to_string_preserves_nulls() {
+    let rb = orders_batch();
+
+    if let EvalResult::StringArray(arr) = eval("to_string(qty)", &rb) {
+        assert_eq!(arr.value(0), "3");
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), "0");
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringArray(arr) = eval("to_string(price)", &rb) {
+        assert_eq!(arr.value(0), "2.5");
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_to_string_constants() {
+    let rb = orders_batch();
+    assert!(matches!(eval("to_string(42)", &rb), EvalResult::StringConst(ref s) if s == "42"));
+    assert!(matches!(eval("to_string(true)", &rb), EvalResult::StringConst(ref s) if s == "true"));
+}
+
+#[test]
+fn test_to_number_nulls_out_invalid_values() {
+    let rb = orders_batch();
+
+    if let EvalResult::Float64Array(arr) = eval("to_number(amount)", &rb) {
+        assert_eq!(arr.value(0), 19.99);
+        assert!(arr.is_null(1)); // "n/a"
+        assert!(arr.is_null(2)); // null stays null
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    // Numbers are returned unchanged
+    assert!(matches!(eval("to_number(qty)", &rb), EvalResult::Int64Array(_)));
+    assert!(matches!(eval("to_number('abc')", &rb), EvalResult::NullConst));
+}
+
+#[test]
+fn test_to_number_of_booleans_is_null() {
+    let rb = orders_batch();
+
+    assert!(matches!(eval("to_number(true)", &rb), EvalResult::NullConst));
+    match eval("to_number(qty > `1`)", &rb) {
+        EvalResult::NullArray(arr) => assert_eq!(arr.len(), 3),
+        other => panic!("Expected NullArray, got {:?}", other),
+    }
+    // Not a conversion error, also null in strict mode
+    let (_, expr) = parse_jmespath("to_number(false)").unwrap();
+    assert!(matches!(eval_jmespath_with_options(&expr, &rb, &strict()), EvalResult::NullConst));
+}
+
+#[test]
+#[should_panic(expected = "to_number() cannot convert")]
+fn test_to_number_strict_mode_errors() {
+    let rb = orders_batch();
+    let (_, expr) = parse_jmespath("to_number(amount)").unwrap();
+    eval_jmespath_with_options(&expr, &rb, &strict());
+}
+
+#[test]
+fn test_to_boolean() {
+    let rb = orders_batch();
+
+    if let EvalResult::BoolArray(arr) = eval("to_boolean(flag)", &rb) {
+        assert!(arr.value(0));
+        assert!(!arr.value(1));
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected BoolArray");
+    }
+
+    if let EvalResult::BoolArray(arr) = eval("to_boolean(qty)", &rb) {
+        assert!(arr.value(0));
+        assert!(arr.is_null(1));
+        assert!(!arr.value(2));
+    } else {
+        panic!("Expected BoolArray");
+    }
+}
+
+#[test]
+fn test_to_timestamp_null_and_error_modes() {
+    let rb = orders_batch();
+
+    if let EvalResult::TimestampArray(arr) = eval("to_timestamp(created)", &rb) {
+        assert!(arr.is_valid(0));
+        assert!(arr.is_null(1));
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected TimestampArray");
+    }
+
+    let (_, expr) = parse_jmespath("to_timestamp(created)").unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        eval_jmespath_with_options(&expr, &rb, &strict());
+    }));
+    assert!(result.is_err());
+
+    // The options only apply to the evaluation they were passed to, even if it panicked
+    assert!(matches!(eval("to_timestamp(created)", &rb), EvalResult::TimestampArray(_)));
+}
+
+#[test]
+fn test_cast_to_named_types() {
+    let rb = orders_batch();
+
+    if let EvalResult::Int64Array(arr) = eval("cast(price, 'int64')", &rb) {
+        assert_eq!(arr.value(0), 2);
+        assert_eq!(arr.value(1), 7);
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    assert!(matches!(eval("cast(qty, 'float64')", &rb), EvalResult::Float64Array(_)));
+    assert!(matches!(eval("cast(id, 'string')", &rb), EvalResult::StringArray(_)));
+    assert!(matches!(eval("cast('2024-01-15', 'date')", &rb), EvalResult::TimestampConst(_)));
+    assert!(matches!(eval("cast('12', 'int32')", &rb), EvalResult::IntConst(12)));
+}
+
+#[test]
+#[should_panic(expected = "unsupported target type")]
+fn test_cast_unknown_type() {
+    let rb = orders_batch();
+    eval("cast(id, 'varchar')", &rb);
+}
+
+#[test]
+fn test_cast_in_multi_select() {
+    let rb = orders_batch();
+
+    if let EvalResult::RecordBatch(result) = eval("{id: id, amount: to_number(amount), qty: to_string(qty)}", &rb) {
+        let json_result = arrow_to_json(&result);
+        assert_eq!(json_result[0]["amount"], 19.99);
+        assert_eq!(json_result[1]["amount"], serde_json::Value::Null);
+        assert_eq!(json_result[0]["qty"], "3");
+        assert_eq!(json_result[1]["qty"], serde_json::Value::Null);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
@@ -0,0 +1,256 @@
+// Tests for date/time support: Timestamp/Date columns, now(), to_timestamp(), date_trunc(), extract(), ts_diff()
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::array::{Array, Date32Array, StringArray, TimestampMicrosecondArray, TimestampMillisecondArray};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Events with a millisecond timestamp column (UTC) and a date column
+fn events_batch() -> RecordBatch {
+    // 2024-03-01T10:15:30Z, 2024-03-01T12:45:00Z, 2024-03-03T08:00:00Z (Sunday)
+    let ts = TimestampMillisecondArray::from(vec![
+        Some(1_709_288_130_000),
+        Some(1_709_297_100_000),
+        Some(1_709_452_800_000),
+    ]).with_timezone("+00:00");
+    // 2024-03-01, 2024-03-02, 2024-03-03
+    let day = Date32Array::from(vec![19783, 19784, 19785]);
+    let name = StringArray::from(vec!["login", "purchase", "logout"]);
+
+    let schema = Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".into())), true),
+        Field::new("day", DataType::Date32, true),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+    RecordBatch::try_new(Arc::new(schema), vec![Arc::new(ts), Arc::new(day), Arc::new(name)]).unwrap()
+}
+
+#[test]
+fn test_path_on_timestamp_and_date_columns() {
+    let rb = events_batch();
+
+    assert!(matches!(eval("ts", &rb), EvalResult::TimestampArray(_)));
+    assert!(matches!(eval("day", &rb), EvalResult::TimestampArray(_)));
+
+    if let EvalResult::StringConst(t) = eval("type(ts)", &rb) {
+        assert_eq!(t, "array<timestamp>");
+    } else {
+        panic!("Expected StringConst");
+    }
+}
+
+#[test]
+fn test_compare_timestamp_with_iso_string() {
+    let rb = events_batch();
+
+    if let EvalResult::BoolArray(mask) = eval("ts > '2024-03-01T11:00:00Z'", &rb) {
+        assert!(!mask.value(0));
+        assert!(mask.value(1));
+        assert!(mask.value(2));
+    } else {
+        panic!("Expected BoolArray");
+    }
+
+    if let EvalResult::BoolArray(mask) = eval("day <= '2024-03-02'", &rb) {
+        assert!(mask.value(0));
+        assert!(mask.value(1));
+        assert!(!mask.value(2));
+    } else {
+        panic!("Expected BoolArray");
+    }
+}
+
+#[test]
+fn test_filter_by_timestamp_range() {
+    let rb = events_batch();
+
+    if let EvalResult::RecordBatch(filtered) = eval("events[?ts >= '2024-03-01T12:00:00Z' && ts < '2024-03-02T00:00:00Z']", &rb) {
+        let json_result = arrow_to_json(&filtered);
+        assert_eq!(filtered.num_rows(), 1);
+        assert_eq!(json_result[0]["name"], "purchase");
+        assert_eq!(json_result[0]["ts"], "2024-03-01T12:45:00Z");
+        assert_eq!(json_result[0]["day"], "2024-03-02");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_compare_with_now() {
+    let rb = events_batch();
+
+    if let EvalResult::BoolArray(mask) = eval("ts < now()", &rb) {
+        assert_eq!(mask.true_count(), 3);
+    } else {
+        panic!("Expected BoolArray");
+    }
+}
+
+#[test]
+fn test_to_timestamp_iso_and_format() {
+    let data = json!([
+        {"created": "2024-01-15T08:30:00Z", "local": "15.01.2024 08:30"},
+        {"created": "2024-06-30T23:59:59Z", "local": "30.06.2024 23:59"}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::BoolArray(mask) = eval("to_timestamp(created) > '2024-03-01T00:00:00Z'", &rb) {
+        assert!(!mask.value(0));
+        assert!(mask.value(1));
+    } else {
+        panic!("Expected BoolArray");
+    }
+
+    if let EvalResult::RecordBatch(result) = eval("{ts: to_timestamp(local, '%d.%m.%Y %H:%M')}", &rb) {
+        let json_result = arrow_to_json(&result);
+        assert_eq!(json_result[0]["ts"], "2024-01-15T08:30:00");
+        assert_eq!(json_result[1]["ts"], "2024-06-30T23:59:00");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_to_timestamp_keeps_nulls() {
+    let data = json!([
+        {"created": "2024-01-15T08:30:00Z"},
+        {"created": null}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::TimestampArray(arr) = eval("to_timestamp(created)", &rb) {
+        assert!(arr.is_valid(0));
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected TimestampArray");
+    }
+}
+
+#[test]
+fn test_date_trunc_hour_and_month() {
+    let rb = events_batch();
+
+    if let EvalResult::RecordBatch(result) = eval("{hour: date_trunc('hour', ts), month: date_trunc('month', ts)}", &rb) {
+        let json_result = arrow_to_json(&result);
+        assert_eq!(json_result[0]["hour"], "2024-03-01T10:00:00Z");
+        assert_eq!(json_result[1]["hour"], "2024-03-01T12:00:00Z");
+        assert_eq!(json_result[2]["month"], "2024-03-01T00:00:00Z");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_date_trunc_respects_timezone() {
+    // 2024-03-01T23:30:00Z is already 2024-03-02 in +02:00
+    let ts = TimestampMillisecondArray::from(vec![1_709_335_800_000]).with_timezone("+02:00");
+    let schema = Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, Some("+02:00".into())), true),
+    ]);
+    let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(ts)]).unwrap();
+
+    if let EvalResult::RecordBatch(result) = eval("{d: date_trunc('day', ts)}", &rb) {
+        let json_result = arrow_to_json(&result);
+        assert_eq!(json_result[0]["d"], "2024-03-02T00:00:00+02:00");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_extract_parts() {
+    let rb = events_batch();
+
+    if let EvalResult::Int32Array(arr) = eval("extract('hour', ts)", &rb) {
+        assert_eq!(arr.value(0), 10);
+        assert_eq!(arr.value(1), 12);
+        assert_eq!(arr.value(2), 8);
+    } else {
+        panic!("Expected Int32Array");
+    }
+
+    if let EvalResult::Int32Array(arr) = eval("extract('dow', ts)", &rb) {
+        assert_eq!(arr.value(0), 5); // Friday
+        assert_eq!(arr.value(2), 0); // Sunday
+    } else {
+        panic!("Expected Int32Array");
+    }
+
+    if let EvalResult::Int64Array(arr) = eval("extract('epoch', ts)", &rb) {
+        assert_eq!(arr.value(0), 1_709_288_130);
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::IntConst(year) = eval("extract('year', '2023-07-04T00:00:00Z')", &rb) {
+        assert_eq!(year, 2023);
+    } else {
+        panic!("Expected IntConst");
+    }
+}
+
+#[test]
+fn test_ts_diff_units() {
+    let rb = events_batch();
+
+    if let EvalResult::Int64Array(arr) = eval("ts_diff(ts, '2024-03-01T00:00:00Z', 'hour')", &rb) {
+        assert_eq!(arr.value(0), 10);
+        assert_eq!(arr.value(1), 12);
+        assert_eq!(arr.value(2), 56);
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::Int64Array(arr) = eval("ts_diff(ts, day)", &rb) {
+        assert_eq!(arr.value(0), 36_930);
+        assert_eq!(arr.value(1), -40_500);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+fn test_ts_diff_overflow_is_null() {
+    let extremes = TimestampMicrosecondArray::from(vec![i64::MAX, 0]);
+    let schema = Schema::new(vec![Field::new("t", DataType::Timestamp(TimeUnit::Microsecond, None), true)]);
+    let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(extremes)]).unwrap();
+
+    if let EvalResult::Int64Array(arr) = eval("ts_diff(t, '1900-01-01T00:00:00Z', 'day')", &rb) {
+        assert!(arr.is_null(0));
+        assert_eq!(arr.value(1), 25_567);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+fn test_compare_literal_before_timestamp() {
+    let rb = events_batch();
+
+    assert_eq!(bools(eval("'2024-03-01T11:00:00Z' < ts", &rb)), bools(eval("ts > '2024-03-01T11:00:00Z'", &rb)));
+    assert_eq!(bools(eval("'2024-03-02' >= day", &rb)), vec![Some(true), Some(true), Some(false)]);
+    assert_eq!(bools(eval("`\"2024-03-01\"` < ts", &rb)), vec![Some(true), Some(true), Some(true)]);
+    assert_eq!(bools(eval("'2024-03-03T08:00:00Z' == ts", &rb)), vec![Some(false), Some(false), Some(true)]);
+}
+
+#[test]
+fn test_sort_and_index_timestamps() {
+    let rb = events_batch();
+
+    if let EvalResult::RecordBatch(sorted) = eval("reverse(sort_by(@, &ts))", &rb) {
+        let json_result = arrow_to_json(&sorted);
+        assert_eq!(json_result[0]["name"], "logout");
+    } else {
+        panic!("Expected RecordBatch");
+    }
+
+    if let EvalResult::TimestampConst(micros) = eval("ts[0]", &rb) {
+        assert_eq!(micros, 1_709_288_130_000_000);
+    } else {
+        panic!("Expected TimestampConst");
+    }
+}
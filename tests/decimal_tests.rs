@@ -0,0 +1,170 @@
+// Tests for Decimal128/Decimal256 columns: paths, exact comparisons, sum/avg, min/max and JSON output
+use arrow_jmespath::{arrow_to_json, arrow_to_json_with_options, DecimalJson, EvalOptions, EvalResult};
+use arrow::array::{Array, ArrayRef, AsArray, Decimal128Array, Decimal256Array, Int32Array};
+use arrow::datatypes::{i256, DataType, Decimal128Type, Decimal256Type, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Decimal constant rendered as text, e.g. "12.3400"
+fn decimal_text(result: EvalResult) -> String {
+    match result {
+        EvalResult::DecimalConst(arr) => match arr.data_type() {
+            DataType::Decimal128(_, _) => arr.as_primitive::<Decimal128Type>().value_as_string(0),
+            _ => arr.as_primitive::<Decimal256Type>().value_as_string(0),
+        },
+        other => panic!("Expected DecimalConst, got {:?}", other),
+    }
+}
+
+/// Ledger with Decimal128(18, 4) amounts (in units of 0.0001)
+fn ledger_batch() -> RecordBatch {
+    let amounts = Decimal128Array::from(vec![Some(100_000), Some(2_5000), None, Some(-1)])
+        .with_precision_and_scale(18, 4)
+        .unwrap();
+    let big = Decimal256Array::from(vec![
+        Some(i256::from_i128(10).wrapping_pow(40)),
+        Some(i256::from_i128(5)),
+        None,
+        Some(i256::from_i128(-5)),
+    ])
+    .with_precision_and_scale(50, 2)
+    .unwrap();
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2, 3, 4]))),
+        ("amount", Arc::new(amounts)),
+        ("big", Arc::new(big)),
+    ];
+    let schema = Schema::new(columns.iter().map(|(n, c)| Field::new(*n, c.data_type().clone(), true)).collect::<Vec<_>>());
+    RecordBatch::try_new(Arc::new(schema), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+fn filtered_ids(query: &str, rb: &RecordBatch) -> Vec<i32> {
+    match eval(query, rb) {
+        EvalResult::RecordBatch(filtered) => filtered.column(0).as_primitive::<arrow::datatypes::Int32Type>().values().to_vec(),
+        other => panic!("Expected RecordBatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decimal_path_and_type() {
+    let rb = ledger_batch();
+
+    if let EvalResult::DecimalArray(arr) = eval("amount", &rb) {
+        assert_eq!(arr.data_type(), &DataType::Decimal128(18, 4));
+    } else {
+        panic!("Expected DecimalArray");
+    }
+    assert!(matches!(eval("type(big)", &rb), EvalResult::StringConst(s) if s == "array<decimal>"));
+    assert_eq!(decimal_text(eval("amount[1]", &rb)), "2.5000");
+}
+
+#[test]
+fn test_decimal_comparisons_are_exact() {
+    let rb = ledger_batch();
+
+    assert_eq!(filtered_ids("rows[?amount > 5]", &rb), vec![1]);
+    assert_eq!(filtered_ids("rows[?amount == 2.5]", &rb), vec![2]);
+    assert_eq!(filtered_ids("rows[?amount < 0]", &rb), vec![4]);
+    assert_eq!(filtered_ids("rows[?amount >= -0.0001]", &rb), vec![1, 2, 4]);
+    assert_eq!(filtered_ids("rows[?big > 1000000]", &rb), vec![1]);
+    assert_eq!(filtered_ids("rows[?big == 0.05]", &rb), vec![2]);
+}
+
+#[test]
+fn test_decimal_sum_and_avg_precision_rules() {
+    let rb = ledger_batch();
+
+    if let EvalResult::DecimalConst(total) = eval("sum(amount)", &rb) {
+        assert_eq!(total.data_type(), &DataType::Decimal128(28, 4));
+    } else {
+        panic!("Expected DecimalConst");
+    }
+    assert_eq!(decimal_text(eval("sum(amount)", &rb)), "12.4999");
+
+    if let EvalResult::DecimalConst(avg) = eval("avg(amount)", &rb) {
+        assert_eq!(avg.data_type(), &DataType::Decimal128(22, 8));
+    } else {
+        panic!("Expected DecimalConst");
+    }
+    // 12.4999 / 3 = 4.16663333...
+    assert_eq!(decimal_text(eval("avg(amount)", &rb)), "4.16663333");
+
+    assert_eq!(decimal_text(eval("sum(big)", &rb)), "100000000000000000000000000000000000000.00");
+    assert!(matches!(eval("avg(amount[2:3])", &rb), EvalResult::NullConst));
+}
+
+#[test]
+fn test_decimal_min_max() {
+    let rb = ledger_batch();
+
+    assert_eq!(decimal_text(eval("min(amount)", &rb)), "-0.0001");
+    assert_eq!(decimal_text(eval("max(amount)", &rb)), "10.0000");
+    assert_eq!(decimal_text(eval("min(big)", &rb)), "-0.05");
+}
+
+#[test]
+fn test_decimal_json_output() {
+    let rb = ledger_batch();
+    let rows = arrow_to_json(&rb);
+
+    // Strings by default, keeping the scale
+    assert_eq!(rows[0]["amount"], json!("10.0000"));
+    assert_eq!(rows[1]["amount"], json!("2.5000"));
+    assert!(rows[2]["amount"].is_null());
+    assert_eq!(rows[3]["big"], json!("-0.05"));
+
+    let options = EvalOptions { decimal_json: DecimalJson::Number, ..Default::default() };
+    let rows = arrow_to_json_with_options(&rb, &options);
+    assert_eq!(rows[0]["amount"], json!(10.0));
+    assert_eq!(rows[1]["amount"], json!(2.5));
+    assert!(rows[2]["amount"].is_null());
+    assert_eq!(rows[3]["big"], json!(-0.05));
+}
+
+/// Decimal128(38, 0) column near the top of its range
+fn overflow_batch() -> RecordBatch {
+    let max = 10_i128.pow(38) - 1;
+    let values = Decimal128Array::from(vec![max, max, -5]).with_precision_and_scale(38, 0).unwrap();
+    RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("v", values.data_type().clone(), true)])),
+        vec![Arc::new(values)],
+    ).unwrap()
+}
+
+#[test]
+fn test_decimal128_overflow_widens_to_decimal256() {
+    let rb = overflow_batch();
+
+    if let EvalResult::DecimalConst(total) = eval("sum(v)", &rb) {
+        assert_eq!(total.data_type(), &DataType::Decimal256(48, 0));
+    } else {
+        panic!("Expected DecimalConst");
+    }
+    assert_eq!(decimal_text(eval("sum(v)", &rb)), "199999999999999999999999999999999999993");
+    assert_eq!(decimal_text(eval("avg(v[0:2])", &rb)), "99999999999999999999999999999999999999.0000");
+}
+
+#[test]
+fn test_decimal_json_uses_one_representation_per_column() {
+    let values = Decimal128Array::from(vec![123_456_789_012_345_678_901_i128, 1_250])
+        .with_precision_and_scale(30, 4)
+        .unwrap();
+    let rb = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("v", values.data_type().clone(), true)])),
+        vec![Arc::new(values)],
+    ).unwrap();
+
+    // f64 would round the first value to 12345678901234568
+    let rows = arrow_to_json(&rb);
+    assert_eq!(rows[0]["v"], json!("12345678901234567.8901"));
+    assert_eq!(rows[1]["v"], json!("0.1250"));
+
+    let options = EvalOptions { decimal_json: DecimalJson::Number, ..Default::default() };
+    let rows = arrow_to_json_with_options(&rb, &options);
+    assert!(rows[0]["v"].is_number());
+    assert_eq!(rows[1]["v"], json!(0.125));
+}
@@ -0,0 +1,118 @@
+// Tests for dictionary-encoded string columns: comparisons, string functions, group_by and JSON output
+use arrow_jmespath::{arrow_to_json, EvalResult};
+use arrow::array::{Array, ArrayRef, DictionaryArray, Int32Array};
+use arrow::datatypes::{Field, Int32Type, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Requests with a dictionary-encoded status column (one null row)
+fn requests_batch() -> RecordBatch {
+    let status: DictionaryArray<Int32Type> =
+        vec![Some("ok"), Some("error"), Some("ok"), None, Some("timeout"), Some("ok")].into_iter().collect();
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5, 6]))),
+        ("status", Arc::new(status)),
+    ];
+    let fields: Vec<Field> = columns.iter()
+        .map(|(name, col)| Field::new(*name, col.data_type().clone(), true))
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+#[test]
+fn test_dictionary_path_and_json_output() {
+    let rb = requests_batch();
+    assert!(matches!(eval("status", &rb), EvalResult::DictionaryArray(_)));
+    assert_eq!(arrow_to_json(&rb)[1], json!({"id": 2, "status": "error"}));
+    assert_eq!(arrow_to_json(&rb)[3], json!({"id": 4, "status": null}));
+}
+
+#[test]
+fn test_dictionary_compare_with_constant() {
+    let rb = requests_batch();
+    assert_eq!(
+        bools(eval("status == 'ok'", &rb)),
+        vec![Some(true), Some(false), Some(true), Some(false), Some(false), Some(true)]
+    );
+    // Constant on the left flips the operator
+    assert_eq!(
+        bools(eval("'f' < status", &rb)),
+        vec![Some(true), Some(false), Some(true), None, Some(true), Some(true)]
+    );
+}
+
+#[test]
+fn test_dictionary_filter() {
+    let rb = requests_batch();
+    match eval("@[?status != 'ok'] | id", &rb) {
+        // The null status is unequal to 'ok' as well
+        EvalResult::Int32Array(arr) => assert_eq!(arr.values().to_vec(), vec![2, 4, 5]),
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_dictionary_string_functions_map_through_keys() {
+    let rb = requests_batch();
+    match eval("length(status)", &rb) {
+        EvalResult::Int32Array(arr) => {
+            assert_eq!(arr.iter().collect::<Vec<_>>(), vec![Some(2), Some(5), Some(2), None, Some(7), Some(2)])
+        },
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+    assert_eq!(
+        bools(eval("starts_with(status, 'o')", &rb)),
+        vec![Some(true), Some(false), Some(true), None, Some(false), Some(true)]
+    );
+}
+
+#[test]
+fn test_dictionary_other_functions_see_decoded_values() {
+    let rb = requests_batch();
+    match eval("sort(status)", &rb) {
+        EvalResult::StringArray(arr) => assert_eq!(
+            arr.iter().collect::<Vec<_>>(),
+            vec![Some("error"), Some("ok"), Some("ok"), Some("ok"), Some("timeout"), None]
+        ),
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_dictionary_group_by() {
+    let rb = requests_batch();
+    assert_eq!(strings(eval("group_by(@, &status)", &rb)), vec![Some("error".to_string()), Some("ok".to_string()), Some("timeout".to_string())]);
+}
+
+#[test]
+fn test_dictionary_group_by_ignores_unused_values() {
+    let rb = requests_batch();
+    assert_eq!(strings(eval("@[?id > `3`] | group_by(@, &status)", &rb)), vec![Some("ok".to_string()), Some("timeout".to_string())]);
+}
+
+#[test]
+fn test_dictionary_compare_matches_plain_column_types() {
+    let dict: DictionaryArray<Int32Type> = vec![Some("5"), Some("7"), None].into_iter().collect();
+    let plain = arrow::array::StringArray::from(vec![Some("5"), Some("7"), None]);
+    let schema = Schema::new(vec![
+        Field::new("code", dict.data_type().clone(), true),
+        Field::new("name", arrow::datatypes::DataType::Utf8, true),
+    ]);
+    let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(dict), Arc::new(plain)]).unwrap();
+
+    for column in ["code", "name"] {
+        // A number never equals a string, even if the text looks the same
+        let query = |q: &str| eval(&q.replace("col", column), &rb);
+        assert_eq!(bools(query("col == `5`")), vec![Some(false), Some(false), Some(false)]);
+        assert_eq!(bools(query("col != `5`")), vec![Some(true), Some(true), Some(true)]);
+        assert_eq!(bools(query("`5` == col")), vec![Some(false), Some(false), Some(false)]);
+        assert_eq!(bools(query("col < `5`")), vec![None, None, None]);
+        assert_eq!(bools(query("col == '5'")), vec![Some(true), Some(false), Some(false)]);
+        assert_eq!(bools(query("'5' == col")), vec![Some(true), Some(false), Some(false)]);
+    }
+    assert!(matches!(eval("'5' == `5`", &rb), EvalResult::BoolConst(false)));
+}
@@ -1,3 +1,4 @@
+#![allow(unused_imports, clippy::bool_assert_comparison)]
 use arrow_jmespath::*;
 use serde_json::json;
 use arrow::array::Array;
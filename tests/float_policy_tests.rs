@@ -0,0 +1,127 @@
+// Tests for the float policy: total order vs IEEE comparisons, NaN in aggregates and non-finite JSON output
+use arrow_jmespath::{
+    parse_jmespath, eval_jmespath_with_options, arrow_to_json, arrow_to_json_with_options, EvalResult,
+    EvalOptions, FloatOrdering, FloatPolicy, NanAggregation, NonFiniteJson,
+};
+use arrow::array::{ArrayRef, Float32Array, Float64Array};
+use arrow::datatypes::{Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+fn eval_with<'a>(query: &str, rb: &'a RecordBatch, float_policy: FloatPolicy) -> EvalResult<'a> {
+    let (_, expr) = parse_jmespath(query).unwrap();
+    eval_jmespath_with_options(&expr, rb, &EvalOptions { float_policy, ..Default::default() })
+}
+
+fn ieee() -> FloatPolicy {
+    FloatPolicy { ordering: FloatOrdering::Ieee, ..Default::default() }
+}
+
+/// Measurements with NaN, signed zero, infinity and null
+fn measurements_batch() -> RecordBatch {
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("x", Arc::new(Float64Array::from(vec![Some(1.0), Some(f64::NAN), Some(-0.0), Some(f64::INFINITY), None]))),
+        ("y", Arc::new(Float32Array::from(vec![Some(2.5), Some(f32::NAN), Some(0.0), Some(f32::NEG_INFINITY), None]))),
+    ];
+    let fields: Vec<Field> = columns.iter()
+        .map(|(name, col)| Field::new(*name, col.data_type().clone(), true))
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+#[test]
+fn test_total_order_comparisons() {
+    let rb = measurements_batch();
+    assert_eq!(bools(eval("x > `100`", &rb)), vec![Some(false), Some(true), Some(false), Some(true), None]);
+    assert_eq!(bools(eval("x == x", &rb)), vec![Some(true), Some(true), Some(true), Some(true), Some(true)]);
+    assert_eq!(bools(eval("x == `0`", &rb))[2], Some(false));
+}
+
+#[test]
+fn test_ieee_comparisons() {
+    let rb = measurements_batch();
+    assert_eq!(bools(eval_with("x > `100`", &rb, ieee())), vec![Some(false), Some(false), Some(false), Some(true), None]);
+    assert_eq!(bools(eval_with("x == x", &rb, ieee())), vec![Some(true), Some(false), Some(true), Some(true), Some(true)]);
+    assert_eq!(bools(eval_with("x != x", &rb, ieee()))[1], Some(true));
+    assert_eq!(bools(eval_with("x == `0`", &rb, ieee()))[2], Some(true));
+    assert_eq!(bools(eval_with("y <= x", &rb, ieee())), vec![Some(false), Some(false), Some(true), Some(true), None]);
+}
+
+#[test]
+fn test_nan_propagates_in_aggregates_by_default() {
+    let rb = measurements_batch();
+    for query in ["min(x)", "max(x)", "sum(x)", "avg(x)", "min(y)"] {
+        assert!(as_float(eval(query, &rb)).is_nan(), "{} should be NaN", query);
+    }
+}
+
+#[test]
+fn test_nan_skipped_in_aggregates() {
+    let rb = measurements_batch();
+    let skip = FloatPolicy { nan_aggregation: NanAggregation::Skip, ..Default::default() };
+    assert_eq!(as_float(eval_with("min(x)", &rb, skip)), -0.0);
+    assert_eq!(as_float(eval_with("max(x)", &rb, skip)), f64::INFINITY);
+    assert_eq!(as_float(eval_with("min(y)", &rb, skip)), f64::NEG_INFINITY);
+    assert_eq!(as_float(eval_with("max(y)", &rb, skip)), 2.5);
+    assert_eq!(as_float(eval_with("avg(y)", &rb, skip)), f64::NEG_INFINITY);
+    let finite = measurements_batch().slice(0, 3);
+    assert_eq!(as_float(eval_with("sum(x)", &finite, skip)), 1.0);
+    assert_eq!(as_float(eval_with("avg(x)", &finite, skip)), 0.5);
+}
+
+#[test]
+fn test_non_finite_json_as_null_by_default() {
+    let rb = measurements_batch();
+    let json = arrow_to_json(&rb);
+    assert_eq!(json[0], json!({"x": 1.0, "y": 2.5}));
+    assert_eq!(json[1], json!({"x": null, "y": null}));
+    assert_eq!(json[3], json!({"x": null, "y": null}));
+}
+
+#[test]
+fn test_non_finite_json_as_strings() {
+    let rb = measurements_batch();
+    let options = EvalOptions {
+        float_policy: FloatPolicy { non_finite_json: NonFiniteJson::String, ..Default::default() },
+        ..Default::default()
+    };
+    let json = arrow_to_json_with_options(&rb, &options);
+    assert_eq!(json[1], json!({"x": "NaN", "y": "NaN"}));
+    assert_eq!(json[3], json!({"x": "Infinity", "y": "-Infinity"}));
+    assert_eq!(json[4], json!({"x": null, "y": null}));
+    // The evaluator's JSON functions follow the same policy
+    let (_, expr) = parse_jmespath("to_json(x)").unwrap();
+    match eval_jmespath_with_options(&expr, &rb, &options) {
+        EvalResult::StringArray(arr) => assert_eq!(arr.value(1), "\"NaN\""),
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_float_aggregates_without_values_are_null() {
+    let rb = measurements_batch();
+    let skip = FloatPolicy { nan_aggregation: NanAggregation::Skip, ..Default::default() };
+    for query in ["min(x[4:])", "max(x[4:])", "avg(x[4:])", "min(x[1:2])", "avg(y[0:0])"] {
+        assert!(matches!(eval_with(query, &rb, skip), EvalResult::NullConst), "{} should be null", query);
+    }
+    assert_eq!(as_float(eval_with("sum(x[4:])", &rb, skip)), 0.0);
+}
+
+#[test]
+fn test_ieee_comparisons_on_dictionary_columns() {
+    use arrow::array::{Array, DictionaryArray, Int32Array};
+    let values = Float64Array::from(vec![-0.0, 0.0, f64::NAN]);
+    let dict = DictionaryArray::<arrow::datatypes::Int32Type>::new(Int32Array::from(vec![0, 1, 2]), Arc::new(values));
+    let rb = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("d", dict.data_type().clone(), true)])),
+        vec![Arc::new(dict)],
+    ).unwrap();
+    assert_eq!(bools(eval("d == `0`", &rb)), vec![Some(false), Some(true), Some(false)]);
+    assert_eq!(bools(eval_with("d == `0`", &rb, ieee())), vec![Some(true), Some(true), Some(false)]);
+    assert_eq!(bools(eval("d > `1`", &rb)), vec![Some(false), Some(false), Some(true)]);
+    assert_eq!(bools(eval_with("d > `1`", &rb, ieee())), vec![Some(false), Some(false), Some(false)]);
+}
@@ -0,0 +1,166 @@
+// Tests for hashing and encoding functions: md5, sha256, xxhash64, hash_bucket, base64_encode/decode, hex
+use arrow_jmespath::{json_to_arrow, EvalResult};
+use arrow::array::{Array, BinaryArray, Int32Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+fn users_batch() -> RecordBatch {
+    let data = json!([
+        {"id": 1, "email": "alice@example.com"},
+        {"id": 2, "email": null},
+        {"id": 3, "email": "hello"}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_md5_and_sha256_hex_digests() {
+    let rb = users_batch();
+
+    if let EvalResult::StringArray(arr) = eval("md5(email)", &rb) {
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), "5d41402abc4b2a76b9719d911017c592");
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringArray(arr) = eval("sha256(email)", &rb) {
+        assert_eq!(arr.value(2), "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringConst(s) = eval("md5('hello')", &rb) {
+        assert_eq!(s, "5d41402abc4b2a76b9719d911017c592");
+    } else {
+        panic!("Expected StringConst");
+    }
+}
+
+#[test]
+fn test_xxhash64_is_stable_across_int_widths() {
+    let rb = users_batch();
+
+    if let EvalResult::Int64Array(arr) = eval("xxhash64(email)", &rb) {
+        // Reference value of XXH64("hello", seed 0) = 0x26c7827d889f6da3
+        assert_eq!(arr.value(2), 0x26c7827d889f6da3u64 as i64);
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    // Int32 column hashes like the same Int64 values
+    let schema = Schema::new(vec![Field::new("id", DataType::Int32, true)]);
+    let rb32 = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(Int32Array::from(vec![1, 2, 3]))]).unwrap();
+    let (h64, h32) = match (eval("xxhash64(id)", &rb), eval("xxhash64(id)", &rb32)) {
+        (EvalResult::Int64Array(a), EvalResult::Int64Array(b)) => (a.clone(), b.clone()),
+        other => panic!("Expected Int64Arrays, got {:?}", other),
+    };
+    assert_eq!(h64, h32);
+}
+
+#[test]
+fn test_hash_bucket_range_and_determinism() {
+    let data: Vec<_> = (0..200).map(|i| json!({"user_id": i})).collect();
+    let rb = json_to_arrow(&json!(data));
+
+    if let EvalResult::Int32Array(buckets) = eval("hash_bucket(user_id, 8)", &rb) {
+        assert_eq!(buckets.len(), 200);
+        assert!(buckets.iter().all(|b| (0..8).contains(&b.unwrap())));
+        // All buckets are used for 200 distinct ids
+        let mut seen = [false; 8];
+        buckets.iter().for_each(|b| seen[b.unwrap() as usize] = true);
+        assert!(seen.iter().all(|s| *s));
+    } else {
+        panic!("Expected Int32Array");
+    }
+
+    // Same input, same bucket
+    let first = eval("hash_bucket(user_id, 8)", &rb);
+    let second = eval("hash_bucket(user_id, 8)", &rb);
+    if let (EvalResult::Int32Array(a), EvalResult::Int32Array(b)) = (first, second) {
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+#[should_panic(expected = "hash_bucket() requires a positive bucket count")]
+fn test_hash_bucket_rejects_zero_buckets() {
+    let rb = users_batch();
+    eval("hash_bucket(id, 0)", &rb);
+}
+
+#[test]
+fn test_base64_roundtrip() {
+    let rb = users_batch();
+
+    if let EvalResult::StringArray(arr) = eval("base64_encode(email)", &rb) {
+        assert_eq!(arr.value(2), "aGVsbG8=");
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringArray(arr) = eval("to_string(base64_decode(base64_encode(email)))", &rb) {
+        assert_eq!(arr.value(0), "alice@example.com");
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), "hello");
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_base64_decode_invalid_is_null() {
+    let data = json!([{"b": "aGVsbG8="}, {"b": "###"}]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::BinaryArray(arr) = eval("base64_decode(b)", &rb) {
+        let arr = arr.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(arr.value(0), b"hello");
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected BinaryArray");
+    }
+}
+
+#[test]
+fn test_hex_for_strings_integers_and_binary() {
+    let schema = Schema::new(vec![
+        Field::new("n", DataType::Int32, true),
+        Field::new("raw", DataType::Binary, true),
+    ]);
+    let rb = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int32Array::from(vec![Some(255), None])),
+            Arc::new(BinaryArray::from(vec![Some(&[0xde, 0xad][..]), Some(&[0x01][..])])),
+        ],
+    ).unwrap();
+
+    if let EvalResult::StringArray(arr) = eval("hex(n)", &rb) {
+        assert_eq!(arr.value(0), "ff");
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringArray(arr) = eval("hex(raw)", &rb) {
+        assert_eq!(arr.value(0), "dead");
+        assert_eq!(arr.value(1), "01");
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    assert!(matches!(eval("md5(raw)", &rb), EvalResult::StringArray(_)));
+
+    if let EvalResult::StringConst(s) = eval("hex('hi')", &rb) {
+        assert_eq!(s, "6869");
+    } else {
+        panic!("Expected StringConst");
+    }
+}
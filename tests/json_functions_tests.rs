@@ -0,0 +1,193 @@
+// Tests for JSON string column functions: parse_json, to_json, json_extract
+use arrow_jmespath::{parse_jmespath, eval_jmespath_with_options, json_to_arrow, arrow_to_json, EvalResult, EvalOptions, ConversionErrorMode};
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+/// Events with a raw JSON payload column
+fn payload_batch() -> RecordBatch {
+    let data = json!([
+        {"id": 1, "payload": "{\"user\": {\"id\": 7, \"name\": \"alice\"}, \"tags\": [\"a\", \"b\"]}"},
+        {"id": 2, "payload": "{\"user\": {\"id\": 9, \"name\": \"bob\"}, \"tags\": []}"},
+        {"id": 3, "payload": null}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_parse_json_objects_to_record_batch() {
+    let rb = payload_batch();
+
+    if let EvalResult::RecordBatch(parsed) = eval("parse_json(payload)", &rb) {
+        assert_eq!(parsed.num_rows(), 3);
+        let json_result = arrow_to_json(&parsed);
+        assert_eq!(json_result[0]["user"]["name"], "alice");
+        assert_eq!(json_result[0]["tags"], json!(["a", "b"]));
+        assert_eq!(json_result[1]["user"]["id"], 9);
+        assert!(json_result[2]["user"].is_null());
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_parse_json_then_path() {
+    let rb = payload_batch();
+
+    if let EvalResult::Int64Array(ids) = eval("parse_json(payload) | user.id", &rb) {
+        assert_eq!(ids.value(0), 7);
+        assert_eq!(ids.value(1), 9);
+        assert!(ids.is_null(2));
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+fn test_parse_json_lists() {
+    let data = json!([{"raw": "[1, 2, 3]"}, {"raw": "[]"}, {"raw": "[4]"}]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::ListArray(list) = eval("parse_json(raw)", &rb) {
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.value(0).len(), 3);
+        assert_eq!(list.value(1).len(), 0);
+    } else {
+        panic!("Expected ListArray");
+    }
+}
+
+#[test]
+fn test_parse_json_invalid_input() {
+    let data = json!([{"raw": "{\"a\": 1}"}, {"raw": "not json"}]);
+    let rb = json_to_arrow(&data);
+
+    // Default: invalid documents become null rows
+    if let EvalResult::Int64Array(a) = eval("parse_json(raw) | a", &rb) {
+        assert_eq!(a.value(0), 1);
+        assert!(a.is_null(1));
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+#[should_panic(expected = "parse_json() cannot parse 'not json' as JSON")]
+fn test_parse_json_invalid_input_error_mode() {
+    let data = json!([{"raw": "{\"a\": 1}"}, {"raw": "not json"}]);
+    let rb = json_to_arrow(&data);
+
+    let (_, expr) = parse_jmespath("parse_json(raw)").unwrap();
+    let options = EvalOptions { on_conversion_error: ConversionErrorMode::Error, ..Default::default() };
+    eval_jmespath_with_options(&expr, &rb, &options);
+}
+
+#[test]
+fn test_to_json_roundtrip() {
+    let rb = payload_batch();
+
+    if let EvalResult::RecordBatch(result) = eval("{id: id, json: to_json(parse_json(payload))}", &rb) {
+        let json_result = arrow_to_json(&result);
+        let first: serde_json::Value = serde_json::from_str(json_result[0]["json"].as_str().unwrap()).unwrap();
+        assert_eq!(first, json!({"user": {"id": 7, "name": "alice"}, "tags": ["a", "b"]}));
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_to_json_list_column_and_rows() {
+    let data = json!([
+        {"name": "a", "scores": [1, 2]},
+        {"name": "b", "scores": [3]}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::StringArray(arr) = eval("to_json(scores)", &rb) {
+        assert_eq!(arr.value(0), "[1,2]");
+        assert_eq!(arr.value(1), "[3]");
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringArray(arr) = eval("to_json(@)", &rb) {
+        assert_eq!(arr.value(1), "{\"name\":\"b\",\"scores\":[3]}");
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_json_extract_paths() {
+    let rb = payload_batch();
+
+    if let EvalResult::StringArray(names) = eval("json_extract(payload, 'user.name')", &rb) {
+        assert_eq!(names.value(0), "alice");
+        assert_eq!(names.value(1), "bob");
+        assert!(names.is_null(2));
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::StringArray(tags) = eval("json_extract(payload, 'tags[1]')", &rb) {
+        assert_eq!(tags.value(0), "b");
+        assert!(tags.is_null(1));
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_json_extract_in_filter_and_constant() {
+    let rb = payload_batch();
+
+    if let EvalResult::RecordBatch(filtered) = eval("events[?json_extract(payload, 'user.id') > 8]", &rb) {
+        assert_eq!(filtered.num_rows(), 1);
+        assert_eq!(arrow_to_json(&filtered)[0]["id"], 2);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+
+    assert!(matches!(eval("json_extract('{\"a\": {\"b\": 5}}', 'a.b')", &rb), EvalResult::Int64Const(5)));
+    assert!(matches!(eval("json_extract('{\"a\": 1}', 'missing')", &rb), EvalResult::NullConst));
+}
+
+#[test]
+fn test_json_extract_escaped_keys_and_nested_values() {
+    let data = json!([
+        {"raw": "{\"a b\": {\"x\\\"y\": [10, {\"z\": true}]}, \"skip\": {\"deep\": [1, 2, 3]}}"},
+        {"raw": "[1, 2]"}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::Int64Array(values) = eval("json_extract(raw, 'a b.x\"y[0]')", &rb) {
+        assert_eq!(values.value(0), 10);
+        assert!(values.is_null(1));
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::BoolArray(values) = eval("json_extract(raw, 'a b.x\"y[1].z')", &rb) {
+        assert!(values.value(0));
+        assert!(values.is_null(1));
+    } else {
+        panic!("Expected BoolArray");
+    }
+}
+
+#[test]
+fn test_parse_json_all_null_column_keeps_length() {
+    use arrow::array::StringArray;
+    use arrow::datatypes::{DataType, Field, Schema};
+    let raw = StringArray::from(vec![None::<&str>, None]);
+    let schema = Schema::new(vec![Field::new("raw", DataType::Utf8, true)]);
+    let rb = RecordBatch::try_new(std::sync::Arc::new(schema), vec![std::sync::Arc::new(raw)]).unwrap();
+
+    match eval("parse_json(raw)", &rb) {
+        EvalResult::NullArray(arr) => assert_eq!(arr.len(), 2),
+        other => panic!("Expected NullArray, got {:?}", other),
+    }
+}
@@ -0,0 +1,198 @@
+// Tests for per-row list functions: unique, array_contains, array_position, array_concat,
+// array_slice, array_sort, array_sum, array_length, length() and flatten on lists
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::array::{Array, Int64Array, LargeListArray};
+use arrow::datatypes::{DataType, Field, Int64Type, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Evaluate a list-producing query and render it as JSON rows via a multi-select hash
+fn eval_lists(query: &str, rb: &RecordBatch) -> Vec<Value> {
+    if let EvalResult::RecordBatch(result) = eval(&format!("{{v: {}}}", query), rb) {
+        arrow_to_json(&result).as_array().unwrap().iter().map(|row| row["v"].clone()).collect()
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+fn orders_batch() -> RecordBatch {
+    let data = json!([
+        {"id": 1, "items": [3, 1, 3, 2], "tags": ["a", "b", "a"], "extra": [9]},
+        {"id": 2, "items": [], "tags": ["c"], "extra": [7, 8]},
+        {"id": 3, "items": null, "tags": null, "extra": [5]}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_length_and_array_length_on_lists() {
+    let rb = orders_batch();
+
+    for query in ["length(items)", "array_length(items)"] {
+        if let EvalResult::Int32Array(arr) = eval(query, &rb) {
+            assert_eq!(arr.value(0), 4);
+            assert_eq!(arr.value(1), 0);
+            assert!(arr.is_null(2));
+        } else {
+            panic!("Expected Int32Array for {}", query);
+        }
+    }
+}
+
+#[test]
+fn test_unique_keeps_first_occurrence() {
+    let rb = orders_batch();
+    let rows = eval_lists("unique(items)", &rb);
+    assert_eq!(rows, vec![json!([3, 1, 2]), json!([]), Value::Null]);
+
+    let rows = eval_lists("unique(tags)", &rb);
+    assert_eq!(rows[0], json!(["a", "b"]));
+}
+
+#[test]
+fn test_array_contains_and_position() {
+    let rb = orders_batch();
+
+    if let EvalResult::BoolArray(mask) = eval("array_contains(tags, 'b')", &rb) {
+        assert!(mask.value(0));
+        assert!(!mask.value(1));
+        assert!(mask.is_null(2));
+    } else {
+        panic!("Expected BoolArray");
+    }
+
+    if let EvalResult::Int32Array(pos) = eval("array_position(items, 3)", &rb) {
+        assert_eq!(pos.value(0), 0);
+        assert!(pos.is_null(1));
+        assert!(pos.is_null(2));
+    } else {
+        panic!("Expected Int32Array");
+    }
+
+    // Works as a filter condition
+    if let EvalResult::RecordBatch(filtered) = eval("orders[?array_contains(items, 2)]", &rb) {
+        assert_eq!(filtered.num_rows(), 1);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_array_contains_with_mismatched_type_never_matches() {
+    let rb = orders_batch();
+
+    if let EvalResult::BoolArray(mask) = eval("array_contains(items, 'x')", &rb) {
+        assert!(!mask.value(0));
+        assert!(!mask.value(1));
+    } else {
+        panic!("Expected BoolArray");
+    }
+}
+
+#[test]
+fn test_array_concat_treats_null_as_empty() {
+    let rb = orders_batch();
+    let rows = eval_lists("array_concat(items, extra)", &rb);
+    assert_eq!(rows, vec![json!([3, 1, 3, 2, 9]), json!([7, 8]), json!([5])]);
+}
+
+#[test]
+fn test_array_slice_and_sort() {
+    let rb = orders_batch();
+
+    let rows = eval_lists("array_slice(items, 1, 3)", &rb);
+    assert_eq!(rows, vec![json!([1, 3]), json!([]), Value::Null]);
+
+    let rows = eval_lists("array_slice(items, -2)", &rb);
+    assert_eq!(rows[0], json!([3, 2]));
+
+    let rows = eval_lists("array_sort(items)", &rb);
+    assert_eq!(rows[0], json!([1, 2, 3, 3]));
+
+    let rows = eval_lists("array_sort(tags, 'desc')", &rb);
+    assert_eq!(rows[0], json!(["b", "a", "a"]));
+}
+
+#[test]
+fn test_array_sum_integers_and_floats() {
+    let rb = orders_batch();
+
+    if let EvalResult::Int64Array(sums) = eval("array_sum(items)", &rb) {
+        assert_eq!(sums.value(0), 9);
+        assert_eq!(sums.value(1), 0);
+        assert!(sums.is_null(2));
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    let data = json!([{"v": [1.5, 2.25]}, {"v": [0.5]}]);
+    let rb = json_to_arrow(&data);
+    if let EvalResult::Float64Array(sums) = eval("array_sum(v)", &rb) {
+        assert_eq!(sums.value(0), 3.75);
+        assert_eq!(sums.value(1), 0.5);
+    } else {
+        panic!("Expected Float64Array");
+    }
+}
+
+#[test]
+#[should_panic(expected = "array_sum() overflowed Int64")]
+fn test_array_sum_overflow_is_an_error() {
+    let rb = json_to_arrow(&json!([{"v": [i64::MAX, 1]}]));
+    eval("array_sum(v)", &rb);
+}
+
+#[test]
+fn test_flatten_string_and_nested_lists() {
+    let rb = orders_batch();
+
+    if let EvalResult::StringArray(arr) = eval("tags[]", &rb) {
+        let values: Vec<&str> = arr.iter().map(|v| v.unwrap()).collect();
+        assert_eq!(values, vec!["a", "b", "a", "c"]);
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    let data = json!([{"m": [[1, 2], [3]]}, {"m": [[4]]}]);
+    let rb = json_to_arrow(&data);
+    assert!(matches!(eval("m[]", &rb), EvalResult::ListArray(_)));
+    if let EvalResult::Int64Array(arr) = eval("m[][]", &rb) {
+        assert_eq!(arr.values().to_vec(), vec![1, 2, 3, 4]);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
+
+#[test]
+fn test_large_list_columns() {
+    let list = LargeListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+        Some(vec![Some(2), Some(2), Some(1)]),
+        None,
+    ]);
+    let schema = Schema::new(vec![Field::new(
+        "l",
+        DataType::LargeList(Arc::new(Field::new("item", DataType::Int64, true))),
+        true,
+    )]);
+    let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(list)]).unwrap();
+
+    if let EvalResult::LargeListArray(arr) = eval("unique(l)", &rb) {
+        let arr = arr.as_any().downcast_ref::<LargeListArray>().unwrap();
+        let first = arr.value(0);
+        assert_eq!(first.as_any().downcast_ref::<Int64Array>().unwrap().values().to_vec(), vec![2, 1]);
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected LargeListArray");
+    }
+
+    assert!(matches!(eval("array_length(l)", &rb), EvalResult::Int32Array(_)));
+    if let EvalResult::Int64Array(arr) = eval("l[]", &rb) {
+        assert_eq!(arr.len(), 3);
+    } else {
+        panic!("Expected Int64Array");
+    }
+}
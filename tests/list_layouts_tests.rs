@@ -0,0 +1,133 @@
+// Tests for LargeList, FixedSizeList and ListView columns: indexing, slicing, projection, flatten, length and JSON output
+use arrow_jmespath::{arrow_to_json, EvalResult};
+use arrow::array::{
+    Array, ArrayRef, FixedSizeListArray, Float32Array, Int32Array, Int64Array, LargeListArray, ListViewArray, StructArray,
+};
+use arrow::buffer::ScalarBuffer;
+use arrow::datatypes::{DataType, Field, Fields, Int64Type, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Documents with embeddings (FixedSizeList), scores (LargeList) and tags (ListView of structs)
+fn documents_batch() -> RecordBatch {
+    let item = Arc::new(Field::new("item", DataType::Float32, true));
+    let embedding = FixedSizeListArray::new(
+        item,
+        3,
+        Arc::new(Float32Array::from(vec![0.1, 0.2, 0.3, 1.0, 2.0, 3.0, 0.0, 0.0, 0.0])),
+        None,
+    );
+    let scores = LargeListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+        Some(vec![Some(5), Some(7)]),
+        Some(vec![]),
+        None,
+    ]);
+    // Views may point anywhere into the values: row 0 reuses the tail of row 1
+    let tag_fields = Fields::from(vec![Field::new("name", DataType::Int32, true)]);
+    let tag_values = StructArray::new(tag_fields.clone(), vec![Arc::new(Int32Array::from(vec![10, 20, 30])) as ArrayRef], None);
+    let tags = ListViewArray::new(
+        Arc::new(Field::new("item", DataType::Struct(tag_fields), true)),
+        ScalarBuffer::from(vec![1, 0, 0]),
+        ScalarBuffer::from(vec![2, 3, 0]),
+        Arc::new(tag_values),
+        None,
+    );
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2, 3]))),
+        ("embedding", Arc::new(embedding)),
+        ("scores", Arc::new(scores)),
+        ("tags", Arc::new(tags)),
+    ];
+    let fields: Vec<Field> = columns.iter()
+        .map(|(name, col)| Field::new(*name, col.data_type().clone(), true))
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+#[test]
+fn test_length_on_all_layouts() {
+    let rb = documents_batch();
+    assert_eq!(ints(eval("length(embedding)", &rb)), vec![Some(3), Some(3), Some(3)]);
+    assert_eq!(ints(eval("length(scores)", &rb)), vec![Some(2), Some(0), None]);
+    assert_eq!(ints(eval("length(tags)", &rb)), vec![Some(2), Some(3), Some(0)]);
+}
+
+#[test]
+fn test_index_row_then_element() {
+    let rb = documents_batch();
+    match eval("embedding[1]", &rb) {
+        EvalResult::Float32Array(arr) => assert_eq!(arr.values().to_vec(), vec![1.0, 2.0, 3.0]),
+        other => panic!("Expected Float32Array, got {:?}", other),
+    }
+    match eval("embedding[1][-1]", &rb) {
+        EvalResult::FloatConst(v) => assert_eq!(v, 3.0),
+        other => panic!("Expected FloatConst, got {:?}", other),
+    }
+    match eval("scores[0][1]", &rb) {
+        EvalResult::Int64Const(v) => assert_eq!(v, 7),
+        other => panic!("Expected Int64Const, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_slice_rows() {
+    let rb = documents_batch();
+    match eval("embedding[0:2]", &rb) {
+        EvalResult::ListArray(arr) => assert_eq!(arr.len(), 2),
+        other => panic!("Expected ListArray, got {:?}", other),
+    }
+    match eval("scores[1:]", &rb) {
+        EvalResult::LargeListArray(arr) => assert_eq!(arr.len(), 2),
+        other => panic!("Expected LargeListArray, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_flatten() {
+    let rb = documents_batch();
+    match eval("scores[]", &rb) {
+        EvalResult::Int64Array(arr) => assert_eq!(arr.as_ref(), &Int64Array::from(vec![5, 7])),
+        other => panic!("Expected Int64Array, got {:?}", other),
+    }
+    match eval("embedding[]", &rb) {
+        EvalResult::Float32Array(arr) => assert_eq!(arr.len(), 9),
+        other => panic!("Expected Float32Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_projection_on_list_view_of_structs() {
+    let rb = documents_batch();
+    let result = eval("tags[*].name", &rb);
+    let arr = match result {
+        EvalResult::ListArray(arr) => arr.clone(),
+        other => panic!("Expected ListArray, got {:?}", other),
+    };
+    let row = |i: usize| arr.value(i).as_any().downcast_ref::<Int32Array>().unwrap().values().to_vec();
+    assert_eq!(row(0), vec![20, 30]);
+    assert_eq!(row(1), vec![10, 20, 30]);
+    assert!(row(2).is_empty());
+}
+
+#[test]
+fn test_flatten_then_field() {
+    let rb = documents_batch();
+    match eval("tags[].name", &rb) {
+        EvalResult::Int32Array(arr) => assert_eq!(arr.values().to_vec(), vec![20, 30, 10, 20, 30]),
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_json_output() {
+    let rb = documents_batch();
+    let json = arrow_to_json(&rb);
+    assert_eq!(json[1]["embedding"], json!([1.0, 2.0, 3.0]));
+    assert_eq!(json[0]["scores"], json!([5, 7]));
+    assert_eq!(json[2]["scores"], json!(null));
+    assert_eq!(json[0]["tags"], json!([{"name": 20}, {"name": 30}]));
+}
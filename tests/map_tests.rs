@@ -0,0 +1,116 @@
+// Tests for Map<Utf8, V> columns: key lookup, quoted keys, keys/values per row, wildcard and JSON output
+use arrow_jmespath::{arrow_to_json, EvalResult};
+use arrow::array::{Array, ArrayRef, Int32Array, MapBuilder, StringBuilder};
+use arrow::datatypes::{Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+fn string_lists(result: EvalResult) -> Vec<Option<Vec<String>>> {
+    match result {
+        EvalResult::ListArray(arr) => (0..arr.len())
+            .map(|i| arr.is_valid(i).then(|| {
+                let values = arr.value(i);
+                let values = values.as_any().downcast_ref::<arrow::array::StringArray>().unwrap();
+                values.iter().map(|v| v.unwrap_or("null").to_string()).collect()
+            }))
+            .collect(),
+        other => panic!("Expected ListArray, got {:?}", other),
+    }
+}
+
+/// Prometheus-style series with a label map (third row has no labels at all)
+fn series_batch() -> RecordBatch {
+    let mut builder = MapBuilder::new(None, StringBuilder::new(), StringBuilder::new());
+    for labels in [
+        Some(vec![("env", "prod"), ("k8s.io/name", "api")]),
+        Some(vec![("k8s.io/name", "worker")]),
+        None,
+        Some(vec![("env", "dev"), ("team", "core")]),
+    ] {
+        match labels {
+            Some(pairs) => {
+                for (k, v) in pairs {
+                    builder.keys().append_value(k);
+                    builder.values().append_value(v);
+                }
+                builder.append(true).unwrap();
+            },
+            None => builder.append(false).unwrap(),
+        }
+    }
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2, 3, 4]))),
+        ("tags", Arc::new(builder.finish())),
+    ];
+    let fields: Vec<Field> = columns.iter()
+        .map(|(name, col)| Field::new(*name, col.data_type().clone(), true))
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+#[test]
+fn test_map_key_lookup() {
+    let rb = series_batch();
+    assert_eq!(strings(eval("tags.env", &rb)), vec![Some("prod".into()), None, None, Some("dev".into())]);
+    assert_eq!(
+        strings(eval("tags.\"k8s.io/name\"", &rb)),
+        vec![Some("api".into()), Some("worker".into()), None, None]
+    );
+}
+
+#[test]
+fn test_map_lookup_in_filter() {
+    let rb = series_batch();
+    match eval("@[?tags.env == 'dev'] | id", &rb) {
+        EvalResult::Int32Array(arr) => assert_eq!(arr.values().to_vec(), vec![4]),
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_map_keys_and_values_per_row() {
+    let rb = series_batch();
+    assert_eq!(
+        string_lists(eval("keys(tags)", &rb)),
+        vec![
+            Some(vec!["env".into(), "k8s.io/name".into()]),
+            Some(vec!["k8s.io/name".into()]),
+            None,
+            Some(vec!["env".into(), "team".into()]),
+        ]
+    );
+    assert_eq!(
+        string_lists(eval("values(tags)", &rb))[3],
+        Some(vec!["dev".into(), "core".into()])
+    );
+}
+
+#[test]
+fn test_map_wildcard_projects_values() {
+    let rb = series_batch();
+    assert_eq!(
+        string_lists(eval("tags.*", &rb)),
+        vec![
+            Some(vec!["prod".into(), "api".into()]),
+            Some(vec!["worker".into()]),
+            None,
+            Some(vec!["dev".into(), "core".into()]),
+        ]
+    );
+}
+
+#[test]
+fn test_map_type_and_json_output() {
+    let rb = series_batch();
+    match eval("type(tags)", &rb) {
+        EvalResult::StringConst(s) => assert_eq!(s, "array<map>"),
+        other => panic!("Expected StringConst, got {:?}", other),
+    }
+    let json = arrow_to_json(&rb);
+    assert_eq!(json[0]["tags"], json!({"env": "prod", "k8s.io/name": "api"}));
+    assert_eq!(json[2]["tags"], json!(null));
+}
@@ -0,0 +1,226 @@
+// Tests for math functions: abs, ceil, floor, round, sqrt, pow, log, exp, sign, clamp, mod
+use arrow_jmespath::{json_to_arrow, EvalResult};
+use arrow::array::{Array, Float32Array, Int32Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Measurements with a null in both the integer and the float column
+fn measurements_batch() -> RecordBatch {
+    let data = json!([
+        {"n": -7, "x": 2.345},
+        {"n": null, "x": -1.5},
+        {"n": 12, "x": null},
+        {"n": 0, "x": 16.0}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_abs_preserves_nulls() {
+    let rb = measurements_batch();
+
+    if let EvalResult::Int64Array(arr) = eval("abs(n)", &rb) {
+        assert_eq!(arr.value(0), 7);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), 12);
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::Float64Array(arr) = eval("abs(x)", &rb) {
+        assert_eq!(arr.value(1), 1.5);
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    assert!(matches!(eval("abs(-3)", &rb), EvalResult::IntConst(3)));
+}
+
+#[test]
+fn test_ceil_floor_round() {
+    let rb = measurements_batch();
+
+    if let EvalResult::Float64Array(arr) = eval("ceil(x)", &rb) {
+        assert_eq!(arr.value(0), 3.0);
+        assert_eq!(arr.value(1), -1.0);
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    if let EvalResult::Float64Array(arr) = eval("floor(x)", &rb) {
+        assert_eq!(arr.value(0), 2.0);
+        assert_eq!(arr.value(1), -2.0);
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    if let EvalResult::Float64Array(arr) = eval("round(x, 2)", &rb) {
+        assert!((arr.value(0) - 2.35).abs() < 1e-12);
+        assert_eq!(arr.value(1), -1.5);
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    // Integers stay integers, negative digits round to tens
+    assert!(matches!(eval("floor(n)", &rb), EvalResult::Int64Array(_)));
+    if let EvalResult::Int64Array(arr) = eval("round(n, -1)", &rb) {
+        assert_eq!(arr.value(0), -10);
+        assert_eq!(arr.value(2), 10);
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    assert_eq!(as_float(eval("round(2.5)", &rb)), 3.0);
+}
+
+#[test]
+fn test_sqrt_exp_log_pow() {
+    let rb = measurements_batch();
+
+    if let EvalResult::Float64Array(arr) = eval("sqrt(x)", &rb) {
+        assert_eq!(arr.value(3), 4.0);
+        assert!(arr.value(1).is_nan());
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    assert_eq!(as_float(eval("exp(0)", &rb)), 1.0);
+    assert_eq!(as_float(eval("log(1)", &rb)), 0.0);
+    assert!((as_float(eval("log(1000, 10)", &rb)) - 3.0).abs() < 1e-12);
+
+    if let EvalResult::Float64Array(arr) = eval("pow(n, 2)", &rb) {
+        assert_eq!(arr.value(0), 49.0);
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected Float64Array");
+    }
+
+    if let EvalResult::Float64Array(arr) = eval("pow(2, n)", &rb) {
+        assert_eq!(arr.value(3), 1.0);
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected Float64Array");
+    }
+}
+
+#[test]
+fn test_sign_and_clamp() {
+    let rb = measurements_batch();
+
+    if let EvalResult::Int64Array(arr) = eval("sign(n)", &rb) {
+        assert_eq!(arr.value(0), -1);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), 1);
+        assert_eq!(arr.value(3), 0);
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::Int64Array(arr) = eval("clamp(n, 0, 10)", &rb) {
+        assert_eq!(arr.value(0), 0);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), 10);
+        assert_eq!(arr.value(3), 0);
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    if let EvalResult::Float64Array(arr) = eval("clamp(x, -1.0, 10.0)", &rb) {
+        assert_eq!(arr.value(0), 2.345);
+        assert_eq!(arr.value(1), -1.0);
+        assert_eq!(arr.value(3), 10.0);
+    } else {
+        panic!("Expected Float64Array");
+    }
+}
+
+#[test]
+fn test_mod_by_zero_is_null() {
+    let data = json!([{"a": 7, "b": 3}, {"a": -7, "b": 3}, {"a": 5, "b": 0}]);
+    let rb = json_to_arrow(&data);
+
+    if let EvalResult::Int64Array(arr) = eval("mod(a, b)", &rb) {
+        assert_eq!(arr.value(0), 1);
+        assert_eq!(arr.value(1), -1);
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected Int64Array");
+    }
+
+    assert!(matches!(eval("mod(10, 4)", &rb), EvalResult::IntConst(2)));
+}
+
+#[test]
+fn test_math_keeps_int32_and_float32_types() {
+    let schema = Schema::new(vec![
+        Field::new("i", DataType::Int32, true),
+        Field::new("f", DataType::Float32, true),
+    ]);
+    let rb = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int32Array::from(vec![Some(-4), None])),
+            Arc::new(Float32Array::from(vec![Some(2.5), Some(-0.5)])),
+        ],
+    ).unwrap();
+
+    if let EvalResult::Int32Array(arr) = eval("abs(i)", &rb) {
+        assert_eq!(arr.value(0), 4);
+        assert!(arr.is_null(1));
+    } else {
+        panic!("Expected Int32Array");
+    }
+
+    if let EvalResult::Float32Array(arr) = eval("ceil(f)", &rb) {
+        assert_eq!(arr.value(0), 3.0);
+        assert_eq!(arr.value(1), -0.0);
+    } else {
+        panic!("Expected Float32Array");
+    }
+
+    assert!(matches!(eval("mod(i, 3)", &rb), EvalResult::Int32Array(_)));
+    assert!(matches!(eval("sqrt(i)", &rb), EvalResult::Float64Array(_)));
+}
+
+#[test]
+fn test_math_on_nulls_and_overflow() {
+    let rb = measurements_batch();
+
+    for query in ["abs(`null`)", "sign(`null`)", "ceil(`null`)", "floor(missing)[0]"] {
+        assert!(matches!(eval(query, &rb), EvalResult::NullConst), "{} should be null", query);
+    }
+    assert!(matches!(eval("abs(missing)", &rb), EvalResult::NullArray(_)));
+
+    let rb = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("i", DataType::Int32, true)])),
+        vec![Arc::new(Int32Array::from(vec![i32::MIN, -3]))],
+    ).unwrap();
+    if let EvalResult::Int32Array(arr) = eval("abs(i)", &rb) {
+        assert!(arr.is_null(0));
+        assert_eq!(arr.value(1), 3);
+    } else {
+        panic!("Expected Int32Array");
+    }
+}
+
+#[test]
+fn test_clamp_widens_float_bounds() {
+    let rb = measurements_batch();
+
+    if let EvalResult::Float64Array(arr) = eval("clamp(n, `0.5`, `9.5`)", &rb) {
+        assert_eq!(arr.value(0), 0.5);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(2), 9.5);
+        assert_eq!(arr.value(3), 0.5);
+    } else {
+        panic!("Expected Float64Array");
+    }
+}
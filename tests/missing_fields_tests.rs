@@ -0,0 +1,141 @@
+// Tests for missing fields: null results by default, errors with MissingFieldMode::Error
+use arrow_jmespath::{
+    parse_jmespath, eval_jmespath_with_options, json_to_arrow, arrow_to_json, EvalResult, EvalOptions,
+    MissingFieldMode,
+};
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn strict() -> EvalOptions {
+    EvalOptions { on_missing_field: MissingFieldMode::Error, ..Default::default() }
+}
+
+/// Users without the optional `nickname` field
+fn users_batch() -> RecordBatch {
+    json_to_arrow(&json!([
+        {"id": 1, "name": "Alice", "address": {"city": "Berlin"}},
+        {"id": 2, "name": "Bob", "address": {"city": "Hamburg"}}
+    ]))
+}
+
+#[test]
+fn test_missing_field_is_null_column() {
+    let rb = users_batch();
+    match eval("nickname", &rb) {
+        EvalResult::NullArray(arr) => {
+            assert_eq!(arr.len(), 2);
+            assert_eq!(arr.logical_null_count(), 2);
+        },
+        other => panic!("Expected NullArray, got {:?}", other),
+    }
+    assert!(matches!(eval("address.country", &rb), EvalResult::NullArray(_)));
+    assert!(matches!(eval("name.first", &rb), EvalResult::NullArray(_)));
+    assert!(matches!(eval("nickname[0]", &rb), EvalResult::NullConst));
+}
+
+#[test]
+fn test_missing_field_comparisons() {
+    let rb = users_batch();
+    assert_eq!(bools(eval("nickname == null", &rb)), vec![Some(true), Some(true)]);
+    assert_eq!(bools(eval("nickname == 'Al'", &rb)), vec![Some(false), Some(false)]);
+    assert_eq!(bools(eval("nickname != name", &rb)), vec![Some(true), Some(true)]);
+    assert_eq!(bools(eval("nickname > `1`", &rb)), vec![None, None]);
+    match eval("@[?nickname == 'Al']", &rb) {
+        EvalResult::RecordBatch(batch) => assert_eq!(batch.num_rows(), 0),
+        other => panic!("Expected RecordBatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_missing_field_in_multi_select() {
+    let rb = users_batch();
+    match eval("{id: id, nick: nickname}", &rb) {
+        EvalResult::RecordBatch(batch) => {
+            assert_eq!(arrow_to_json(&batch), json!([{"id": 1, "nick": null}, {"id": 2, "nick": null}]))
+        },
+        other => panic!("Expected RecordBatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_strict_mode_accepts_existing_fields() {
+    let rb = users_batch();
+    let (_, expr) = parse_jmespath("address.city").unwrap();
+    assert!(matches!(eval_jmespath_with_options(&expr, &rb, &strict()), EvalResult::StringArray(_)));
+}
+
+#[test]
+#[should_panic(expected = "Field 'nickname' not found (path: nickname)")]
+fn test_strict_mode_rejects_missing_field() {
+    let rb = users_batch();
+    let (_, expr) = parse_jmespath("nickname").unwrap();
+    eval_jmespath_with_options(&expr, &rb, &strict());
+}
+
+#[test]
+#[should_panic(expected = "Field 'country' not found (path: address.country)")]
+fn test_strict_mode_rejects_missing_nested_field() {
+    let rb = users_batch();
+    let (_, expr) = parse_jmespath("address.country").unwrap();
+    eval_jmespath_with_options(&expr, &rb, &strict());
+}
+
+#[test]
+fn test_missing_projection_base_is_null() {
+    let rb = users_batch();
+    // A typo'd base is a missing field, not the batch itself
+    assert!(matches!(eval("peple[*].id", &rb), EvalResult::NullArray(_)));
+    assert!(matches!(eval("bogus[*]", &rb), EvalResult::NullArray(_)));
+    match eval("@[*].id", &rb) {
+        EvalResult::Int64Array(ids) => assert_eq!(ids.len(), 2),
+        other => panic!("Expected Int64Array, got {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Field 'bogus' not found (path: bogus)")]
+fn test_strict_mode_rejects_missing_projection_base() {
+    let rb = users_batch();
+    let (_, expr) = parse_jmespath("bogus[*]").unwrap();
+    eval_jmespath_with_options(&expr, &rb, &strict());
+}
+
+#[test]
+fn test_missing_field_in_aggregates() {
+    let rb = users_batch();
+    for query in ["count(nickname)", "count_distinct(nickname)", "count(not_null(nickname))", "count(`null`)"] {
+        assert!(matches!(eval(query, &rb), EvalResult::Int64Const(0)), "{}", query);
+    }
+    for query in [
+        "sum(nickname)", "avg(nickname)", "min(nickname)", "max(nickname)", "median(nickname)",
+        "percentile(nickname, `0.5`)", "variance(nickname)", "stddev(nickname)", "mode(nickname)",
+        "sum(`null`)",
+    ] {
+        assert!(matches!(eval(query, &rb), EvalResult::NullConst), "{}", query);
+    }
+}
+
+#[test]
+fn test_missing_field_in_row_functions() {
+    let rb = users_batch();
+    for query in [
+        "length(nickname)", "contains(nickname, 'a')", "starts_with(nickname, 'a')", "ends_with(nickname, 'a')",
+        "contains(name, nickname)", "parse_json(nickname)", "json_extract(nickname, 'a')",
+        "to_timestamp(nickname)", "date_trunc('day', nickname)", "extract('year', nickname)",
+        "ts_diff(nickname, '2024-01-01')", "unique(nickname)", "array_contains(nickname, `1`)",
+        "array_position(nickname, `1`)", "array_concat(nickname, nickname)", "array_slice(nickname, `0`)",
+        "array_sort(nickname)", "array_sum(nickname)", "array_length(nickname)", "sort(nickname)",
+        "reverse(nickname)",
+    ] {
+        match eval(query, &rb) {
+            EvalResult::NullArray(arr) => assert_eq!(arr.len(), 2, "{}", query),
+            other => panic!("Expected NullArray for {}, got {:?}", query, other),
+        }
+    }
+    assert!(matches!(eval("length(`null`)", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("contains(`null`, 'a')", &rb), EvalResult::NullConst));
+}
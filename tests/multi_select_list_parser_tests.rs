@@ -0,0 +1,66 @@
+// Tests pinning how brackets parse: a leading index, slice, projection, filter or flatten
+// applies to the current node, any other leading '[' starts a multi-select list
+use arrow_jmespath::*;
+
+fn parse(query: &str) -> JmesPathExpr {
+    let (rest, expr) = parse_jmespath(query).unwrap();
+    assert!(rest.is_empty(), "unparsed input for {}: {:?}", query, rest);
+    expr
+}
+
+fn is_path(expr: &JmesPathExpr, name: &str) -> bool {
+    matches!(expr, JmesPathExpr::Path(parts) if parts.len() == 1 && parts[0] == name)
+}
+
+#[test]
+fn test_brackets_after_path_are_unchanged() {
+    assert!(matches!(parse("a[0]"), JmesPathExpr::Index(base, 0) if is_path(&base, "a")));
+    assert!(matches!(parse("a[-1]"), JmesPathExpr::Index(base, -1) if is_path(&base, "a")));
+    assert!(matches!(parse("a[1:3]"), JmesPathExpr::Slice { base, start: Some(1), stop: Some(3), step: None } if is_path(&base, "a")));
+    assert!(matches!(parse("a[*]"), JmesPathExpr::Projection(base, field)
+        if is_path(&base, "a") && matches!(field.as_ref(), JmesPathExpr::Path(parts) if parts.is_empty())));
+    assert!(matches!(parse("a[*].b"), JmesPathExpr::Projection(base, field) if is_path(&base, "a") && is_path(&field, "b")));
+    assert!(matches!(parse("a[?b > `1`]"), JmesPathExpr::Filter(base, condition)
+        if is_path(&base, "a") && matches!(condition.as_ref(), JmesPathExpr::Compare { op: CompareOp::Gt, .. })));
+    assert!(matches!(parse("a[]"), JmesPathExpr::Flatten(base) if is_path(&base, "a")));
+}
+
+#[test]
+fn test_brackets_after_current_node_and_function() {
+    assert!(matches!(parse("@[0]"), JmesPathExpr::Index(base, 0) if matches!(base.as_ref(), JmesPathExpr::CurrentNode)));
+    assert!(matches!(parse("@[?a]"), JmesPathExpr::Filter(base, _) if matches!(base.as_ref(), JmesPathExpr::CurrentNode)));
+    assert!(matches!(parse("sort(a)[0]"), JmesPathExpr::Index(base, 0) if matches!(base.as_ref(), JmesPathExpr::Func { .. })));
+}
+
+fn is_current(expr: &JmesPathExpr) -> bool {
+    matches!(expr, JmesPathExpr::CurrentNode)
+}
+
+#[test]
+fn test_leading_bracket_operations_apply_to_current_node() {
+    assert!(matches!(parse("[0]"), JmesPathExpr::Index(base, 0) if is_current(&base)));
+    assert!(matches!(parse("[-1]"), JmesPathExpr::Index(base, -1) if is_current(&base)));
+    assert!(matches!(parse("[1:3]"), JmesPathExpr::Slice { base, start: Some(1), stop: Some(3), step: None } if is_current(&base)));
+    assert!(matches!(parse("[*]"), JmesPathExpr::Projection(base, field)
+        if is_current(&base) && matches!(field.as_ref(), JmesPathExpr::Path(parts) if parts.is_empty())));
+    assert!(matches!(parse("[*].b"), JmesPathExpr::Projection(base, field) if is_current(&base) && is_path(&field, "b")));
+    assert!(matches!(parse("[?a > `1`]"), JmesPathExpr::Filter(base, condition)
+        if is_current(&base) && matches!(condition.as_ref(), JmesPathExpr::Compare { op: CompareOp::Gt, .. })));
+    assert!(matches!(parse("[]"), JmesPathExpr::Flatten(base) if is_current(&base)));
+    assert!(matches!(parse("[0][1]"), JmesPathExpr::Index(base, 1)
+        if matches!(base.as_ref(), JmesPathExpr::Index(inner, 0) if is_current(inner))));
+}
+
+#[test]
+fn test_leading_bracket_is_a_multi_select_list() {
+    match parse("[a, b[0]]") {
+        JmesPathExpr::MultiSelectList(items) => {
+            assert_eq!(items.len(), 2);
+            assert!(is_path(&items[0], "a"));
+            assert!(matches!(&items[1], JmesPathExpr::Index(base, 0) if is_path(base, "b")));
+        },
+        other => panic!("Expected MultiSelectList, got {:?}", other),
+    }
+    assert!(matches!(parse("[a]"), JmesPathExpr::MultiSelectList(items) if items.len() == 1 && is_path(&items[0], "a")));
+    assert!(matches!(parse("[a, b][0]"), JmesPathExpr::Index(base, 0) if matches!(base.as_ref(), JmesPathExpr::MultiSelectList(_))));
+}
@@ -0,0 +1,100 @@
+// Tests pinning the null policy: comparisons, logic, filters and per-row functions
+use arrow_jmespath::{json_to_arrow, EvalResult};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+/// Pairs covering value/value, null/null, value/null and null/value
+fn pairs_batch() -> RecordBatch {
+    json_to_arrow(&json!([
+        {"id": 1, "a": 1, "b": 1, "s": "x"},
+        {"id": 2, "a": null, "b": null, "s": null},
+        {"id": 3, "a": 2, "b": null, "s": "y"},
+        {"id": 4, "a": null, "b": 3, "s": null}
+    ]))
+}
+
+fn filtered_ids(query: &str, rb: &RecordBatch) -> Vec<i64> {
+    match eval(&format!("@[?{}] | id", query), rb) {
+        EvalResult::Int64Array(arr) => arr.values().to_vec(),
+        other => panic!("Expected Int64Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_equality_is_never_null() {
+    let rb = pairs_batch();
+    assert_eq!(bools(eval("a == b", &rb)), vec![Some(true), Some(true), Some(false), Some(false)]);
+    assert_eq!(bools(eval("a != b", &rb)), vec![Some(false), Some(false), Some(true), Some(true)]);
+    assert_eq!(bools(eval("s == 'x'", &rb)), vec![Some(true), Some(false), Some(false), Some(false)]);
+    assert_eq!(bools(eval("a == `null`", &rb)), vec![Some(false), Some(true), Some(false), Some(true)]);
+}
+
+#[test]
+fn test_null_literals() {
+    let rb = pairs_batch();
+    assert!(matches!(eval("`null` == `null`", &rb), EvalResult::BoolConst(true)));
+    assert!(matches!(eval("`null` != `null`", &rb), EvalResult::BoolConst(false)));
+    assert!(matches!(eval("`null` < `1`", &rb), EvalResult::NullConst));
+}
+
+#[test]
+fn test_ordering_with_null_is_null() {
+    let rb = pairs_batch();
+    assert_eq!(bools(eval("a < b", &rb)), vec![Some(false), None, None, None]);
+    assert_eq!(bools(eval("a >= `1`", &rb)), vec![Some(true), None, Some(true), None]);
+}
+
+#[test]
+fn test_logic_treats_null_as_false() {
+    let rb = pairs_batch();
+    assert_eq!(bools(eval("a > `0` || b > `0`", &rb)), vec![Some(true), Some(false), Some(true), Some(true)]);
+    assert_eq!(bools(eval("a > `0` && b > `0`", &rb)), vec![Some(true), Some(false), Some(false), Some(false)]);
+    assert_eq!(bools(eval("!(a > `1`)", &rb)), vec![Some(true), Some(true), Some(false), Some(true)]);
+}
+
+#[test]
+fn test_filter_keeps_true_rows_only() {
+    let rb = pairs_batch();
+    assert_eq!(filtered_ids("a > `1`", &rb), vec![3]);
+    assert_eq!(filtered_ids("a == b", &rb), vec![1, 2]);
+    assert_eq!(filtered_ids("s != 'x'", &rb), vec![2, 3, 4]);
+    assert_eq!(filtered_ids("!(a > `1`)", &rb), vec![1, 2, 4]);
+}
+
+#[test]
+fn test_string_functions_keep_nulls() {
+    let rb = pairs_batch();
+    assert_eq!(bools(eval("contains(s, 'x')", &rb)), vec![Some(true), None, Some(false), None]);
+    assert_eq!(bools(eval("starts_with(s, 'y')", &rb)), vec![Some(false), None, Some(true), None]);
+    match eval("length(s)", &rb) {
+        EvalResult::Int32Array(arr) => assert_eq!(arr.iter().collect::<Vec<_>>(), vec![Some(1), None, Some(1), None]),
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+    match eval("to_string(a)", &rb) {
+        EvalResult::StringArray(arr) => {
+            assert_eq!(arr.iter().collect::<Vec<_>>(), vec![Some("1"), None, Some("2"), None])
+        },
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_filter_uses_truthiness() {
+    let rb = pairs_batch();
+    assert_eq!(filtered_ids("s", &rb), vec![1, 3]);
+    assert_eq!(filtered_ids("a", &rb), vec![1, 3]);
+    assert_eq!(filtered_ids("nickname", &rb), Vec::<i64>::new());
+    assert_eq!(filtered_ids("`null`", &rb), Vec::<i64>::new());
+    assert_eq!(filtered_ids("`true`", &rb), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_min_max_without_values_is_null() {
+    let rb = pairs_batch();
+    assert!(matches!(eval("min(a[0:0])", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("max(b[1:3])", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("max(a)", &rb), EvalResult::Int64Const(2)));
+}
@@ -1,3 +1,4 @@
+#![allow(clippy::approx_constant)]
 use arrow_jmespath::*;
 
 #[cfg(test)]
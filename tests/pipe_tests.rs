@@ -0,0 +1,126 @@
+// Tests for pipes with non-batch left-hand sides and projections ending at the pipe
+use arrow_jmespath::{json_to_arrow, arrow_to_json, parse_jmespath, EvalResult, JmesPathExpr};
+use arrow::array::{Array, StringArray};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn shop_batch() -> RecordBatch {
+    let data = json!([
+        {"name": "mia", "price": 12.5, "orders": [{"total": 3}, {"total": 4}]},
+        {"name": "al", "price": 7.0, "orders": []},
+        {"name": "zoe", "price": 30.0, "orders": [{"total": 10}]}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_pipe_binds_column_to_current_node() {
+    let rb = shop_batch();
+
+    if let EvalResult::StringArray(names) = eval("name | sort(@)", &rb) {
+        let values: Vec<&str> = names.iter().map(|v| v.unwrap()).collect();
+        assert_eq!(values, vec!["al", "mia", "zoe"]);
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    assert!(matches!(eval("price | max(@)", &rb), EvalResult::FloatConst(v) if v == 30.0));
+    assert!(matches!(eval("price | count(@)", &rb), EvalResult::Int64Const(3)));
+}
+
+#[test]
+fn test_pipe_binds_scalar_and_chains() {
+    let rb = shop_batch();
+
+    assert!(matches!(eval("min(price) | abs(@)", &rb), EvalResult::FloatConst(v) if v == 7.0));
+    assert!(matches!(eval("name | sort(@) | @[0]", &rb), EvalResult::StringConst(s) if s == "al"));
+    assert!(matches!(eval("price | @[?@ > 10.0] | count(@)", &rb), EvalResult::Int64Const(2)));
+}
+
+#[test]
+fn test_projection_stops_at_pipe() {
+    let rb = shop_batch();
+
+    if let EvalResult::StringArray(names) = eval("@[*].name", &rb) {
+        assert_eq!(names.len(), 3);
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    assert!(matches!(eval("@[*].price | sum(@)", &rb), EvalResult::FloatConst(v) if v == 49.5));
+
+    if let EvalResult::StringArray(names) = eval("@[*].name | reverse(@)", &rb) {
+        assert_eq!(names.value(0), "zoe");
+    } else {
+        panic!("Expected StringArray");
+    }
+}
+
+#[test]
+fn test_projection_on_list_of_objects() {
+    let rb = shop_batch();
+
+    if let EvalResult::ListArray(totals) = eval("orders[*].total", &rb) {
+        assert_eq!(totals.len(), 3);
+        assert_eq!(totals.value(0).len(), 2);
+        assert_eq!(totals.value(1).len(), 0);
+    } else {
+        panic!("Expected ListArray");
+    }
+
+    if let EvalResult::RecordBatch(result) = eval("{t: orders[*].total}", &rb) {
+        let rows = arrow_to_json(&result);
+        let values: Vec<_> = rows.as_array().unwrap().iter().map(|r| r["t"].clone()).collect();
+        assert_eq!(json!(values), json!([[3, 4], [], [10]]));
+    } else {
+        panic!("Expected RecordBatch");
+    }
+
+    assert!(matches!(eval("orders[*].total | sum(@)", &rb), EvalResult::Int64Const(17)));
+    assert!(matches!(eval("orders[*].total | max(@)", &rb), EvalResult::Int64Const(10)));
+    assert!(matches!(eval("orders[*].total | @[] | sum(@)", &rb), EvalResult::Int64Const(17)));
+}
+
+#[test]
+fn test_flatten_ends_projection() {
+    let rb = shop_batch();
+
+    match eval("orders[*].total[]", &rb) {
+        EvalResult::Int64Array(totals) => assert_eq!(totals.values().to_vec(), vec![3, 4, 10]),
+        other => panic!("Expected Int64Array, got {:?}", other),
+    }
+    assert!(matches!(eval("orders[*].total[] | sum(@)", &rb), EvalResult::Int64Const(17)));
+}
+
+#[test]
+fn test_flatten_projects_following_path() {
+    let rb = shop_batch();
+
+    // The path after [] belongs to the expression
+    let (rest, expr) = parse_jmespath("orders[].total").unwrap();
+    assert!(rest.is_empty(), "unparsed input: {:?}", rest);
+    assert!(matches!(expr, JmesPathExpr::Projection(base, _) if matches!(base.as_ref(), JmesPathExpr::Flatten(_))));
+
+    match eval("orders[].total", &rb) {
+        EvalResult::Int64Array(totals) => assert_eq!(totals.values().to_vec(), vec![3, 4, 10]),
+        other => panic!("Expected Int64Array, got {:?}", other),
+    }
+    assert!(matches!(eval("orders[].total | sum(@)", &rb), EvalResult::Int64Const(17)));
+}
+
+#[test]
+fn test_pipe_from_single_row() {
+    let rb = shop_batch();
+
+    assert!(matches!(eval("sort_by(@, &price)[0] | name", &rb), EvalResult::StringConst(s) if s == "al"));
+
+    if let EvalResult::StructConst(row) = eval("@[-1] | @", &rb) {
+        let names = row.column_by_name("name").unwrap().as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(names.value(0), "zoe");
+    } else {
+        panic!("Expected StructConst");
+    }
+}
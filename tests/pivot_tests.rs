@@ -0,0 +1,97 @@
+// Tests for reshaping functions: pivot, unpivot
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn sales_batch() -> RecordBatch {
+    let data = json!([
+        {"region": "west", "month": "feb", "revenue": 10},
+        {"region": "east", "month": "jan", "revenue": 5},
+        {"region": "west", "month": "jan", "revenue": 7},
+        {"region": "west", "month": "feb", "revenue": 3},
+        {"region": "east", "month": "jan", "revenue": 1},
+        {"region": null, "month": "jan", "revenue": 100}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_pivot_sum_per_cell() {
+    let rb = sales_batch();
+    let rows = eval_rows("pivot(@, &region, &month, &sum(revenue))", &rb);
+
+    assert_eq!(rows, vec![
+        json!({"region": "east", "feb": null, "jan": 6}),
+        json!({"region": "west", "feb": 13, "jan": 7}),
+    ]);
+}
+
+#[test]
+fn test_pivot_column_order_and_count() {
+    let rb = sales_batch();
+
+    if let EvalResult::RecordBatch(result) = eval("pivot(@, &region, &month, &count(revenue))", &rb) {
+        let names: Vec<String> = result.schema().fields().iter().map(|f| f.name().clone()).collect();
+        assert_eq!(names, vec!["region", "feb", "jan"]);
+        let rows = arrow_to_json(&result);
+        assert_eq!(rows[1]["feb"], 2);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_pivot_after_filter_with_numeric_column_keys() {
+    let data = json!([
+        {"user": "a", "year": 2023, "score": 1.5},
+        {"user": "a", "year": 2024, "score": 2.5},
+        {"user": "b", "year": 2024, "score": 4.0}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    let rows = eval_rows("rows[?score > 1.0] | pivot(@, &user, &year, &avg(score))", &rb);
+    assert_eq!(rows[0], json!({"user": "a", "2023": 1.5, "2024": 2.5}));
+    assert_eq!(rows[1], json!({"user": "b", "2023": null, "2024": 4.0}));
+}
+
+#[test]
+fn test_unpivot_columns_to_rows() {
+    let data = json!([
+        {"id": 1, "q1": 10, "q2": 20, "q3": null},
+        {"id": 2, "q1": 30, "q2": 40, "q3": 50}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    let rows = eval_rows("unpivot(@, ['q1', 'q2', 'q3'], 'quarter', 'value')", &rb);
+    assert_eq!(rows, vec![
+        json!({"id": 1, "quarter": "q1", "value": 10}),
+        json!({"id": 1, "quarter": "q2", "value": 20}),
+        json!({"id": 1, "quarter": "q3", "value": null}),
+        json!({"id": 2, "quarter": "q1", "value": 30}),
+        json!({"id": 2, "quarter": "q2", "value": 40}),
+        json!({"id": 2, "quarter": "q3", "value": 50}),
+    ]);
+}
+
+#[test]
+fn test_unpivot_mixed_numeric_columns_use_common_type() {
+    let data = json!([{"k": "x", "a": 1, "b": 2.5}]);
+    let rb = json_to_arrow(&data);
+
+    let rows = eval_rows("unpivot(@, [a, b], 'col', 'v')", &rb);
+    assert_eq!(rows, vec![
+        json!({"k": "x", "col": "a", "v": 1.0}),
+        json!({"k": "x", "col": "b", "v": 2.5}),
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "unpivot() field 'q9' not found in RecordBatch")]
+fn test_unpivot_unknown_column() {
+    let data = json!([{"id": 1, "q1": 10}]);
+    let rb = json_to_arrow(&data);
+    eval("unpivot(@, ['q9'], 'quarter', 'value')", &rb);
+}
@@ -0,0 +1,130 @@
+// Tests for Int8/Int16, UInt8-UInt64 and Float16 columns in paths, comparisons, aggregates and JSON output
+use arrow_jmespath::{arrow_to_json, EvalResult};
+use arrow::array::{Array, ArrayRef, Float32Array, Int16Array, Int8Array, UInt16Array, UInt32Array, UInt64Array, UInt8Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Sensor readings with one column per primitive width
+fn sensor_batch() -> RecordBatch {
+    let half = arrow::compute::cast(&Float32Array::from(vec![Some(1.5), None, Some(-2.0)]), &DataType::Float16).unwrap();
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("i8", Arc::new(Int8Array::from(vec![Some(-5), Some(3), None]))),
+        ("i16", Arc::new(Int16Array::from(vec![300, -200, 100]))),
+        ("u8", Arc::new(UInt8Array::from(vec![Some(250), Some(10), None]))),
+        ("u16", Arc::new(UInt16Array::from(vec![1, 2, 60000]))),
+        ("u32", Arc::new(UInt32Array::from(vec![7, 4_000_000_000, 9]))),
+        ("u64", Arc::new(UInt64Array::from(vec![u64::MAX, 1, 2]))),
+        ("f16", half),
+    ];
+    let schema = Schema::new(columns.iter().map(|(n, c)| Field::new(*n, c.data_type().clone(), true)).collect::<Vec<_>>());
+    RecordBatch::try_new(Arc::new(schema), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+#[test]
+fn test_paths_and_type_names() {
+    let rb = sensor_batch();
+
+    for (query, expected) in [
+        ("type(i8)", "array<int8>"),
+        ("type(u16)", "array<uint16>"),
+        ("type(u64)", "array<uint64>"),
+        ("type(f16)", "array<float16>"),
+    ] {
+        assert!(matches!(eval(query, &rb), EvalResult::StringConst(s) if s == expected), "{}", query);
+    }
+
+    if let EvalResult::NumericArray(arr) = eval("u8", &rb) {
+        assert_eq!(arr.data_type(), &DataType::UInt8);
+        assert!(arr.is_null(2));
+    } else {
+        panic!("Expected NumericArray");
+    }
+}
+
+#[test]
+fn test_comparisons_with_literals_widen_without_overflow() {
+    let rb = sensor_batch();
+
+    let count = |query: &str| match eval(query, &rb) {
+        EvalResult::RecordBatch(filtered) => filtered.num_rows(),
+        other => panic!("Expected RecordBatch, got {:?}", other),
+    };
+
+    assert_eq!(count("rows[?i8 < 0]"), 1);
+    assert_eq!(count("rows[?u8 < 300]"), 2);
+    assert_eq!(count("rows[?u32 > `3000000000`]"), 1);
+    assert_eq!(count("rows[?u64 > 5]"), 1);
+    assert_eq!(count("rows[?u64 == u64]"), 3);
+    assert_eq!(count("rows[?i16 > u16]"), 1);
+    assert_eq!(count("rows[?f16 >= 1.5]"), 1);
+    assert_eq!(count("rows[?f16 < 0]"), 1);
+}
+
+#[test]
+fn test_mixed_int_and_float_comparisons() {
+    let data = serde_json::json!([{"x": 1.5}, {"x": 0.5}]);
+    let rb = arrow_jmespath::json_to_arrow(&data);
+
+    if let EvalResult::RecordBatch(filtered) = eval("rows[?x > 1]", &rb) {
+        assert_eq!(filtered.num_rows(), 1);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+    assert!(matches!(eval("`2` > `1.5`", &rb), EvalResult::BoolConst(true)));
+}
+
+#[test]
+fn test_aggregates_on_all_widths() {
+    let rb = sensor_batch();
+
+    assert!(matches!(eval("min(i8)", &rb), EvalResult::Int64Const(-5)));
+    assert!(matches!(eval("max(u8)", &rb), EvalResult::Int64Const(250)));
+    assert!(matches!(eval("sum(i16)", &rb), EvalResult::Int64Const(200)));
+    assert!(matches!(eval("sum(u32)", &rb), EvalResult::Int64Const(4_000_000_016)));
+    assert!(matches!(eval("max(u64)", &rb), EvalResult::FloatConst(v) if v == u64::MAX as f64));
+    assert!(matches!(eval("min(f16)", &rb), EvalResult::FloatConst(v) if v == -2.0));
+    assert!(matches!(eval("sum(f16)", &rb), EvalResult::FloatConst(v) if v == -0.5));
+    assert!(matches!(eval("avg(u16)", &rb), EvalResult::FloatConst(v) if v == 20001.0));
+    assert!(matches!(eval("count(i8)", &rb), EvalResult::Int64Const(2)));
+}
+
+#[test]
+#[should_panic(expected = "sum() overflowed Int64")]
+fn test_sum_overflow_is_an_error() {
+    let rb = sensor_batch();
+    eval("sum(u64)", &rb);
+}
+
+#[test]
+fn test_math_and_sorting_keep_types() {
+    let rb = sensor_batch();
+
+    if let EvalResult::NumericArray(arr) = eval("abs(i8)", &rb) {
+        assert_eq!(arr.data_type(), &DataType::Int8);
+    } else {
+        panic!("Expected NumericArray");
+    }
+
+    if let EvalResult::RecordBatch(sorted) = eval("sort_by(@, &u16)", &rb) {
+        let rows = arrow_to_json(&sorted);
+        assert_eq!(rows[2]["u16"], 60000);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_json_output_for_all_widths() {
+    let rb = sensor_batch();
+    let rows = arrow_to_json(&rb);
+
+    assert_eq!(rows[0], json!({
+        "i8": -5, "i16": 300, "u8": 250, "u16": 1, "u32": 7, "u64": u64::MAX, "f16": 1.5
+    }));
+    assert!(rows[1]["f16"].is_null());
+}
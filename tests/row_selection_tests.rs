@@ -0,0 +1,100 @@
+// Tests for row selection functions: top_k, bottom_k, distinct, distinct_by
+use arrow_jmespath::json_to_arrow;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn players_batch() -> RecordBatch {
+    let data = json!([
+        {"name": "ann", "team": "red", "score": 40},
+        {"name": "bob", "team": "blue", "score": 95},
+        {"name": "cid", "team": "red", "score": null},
+        {"name": "dan", "team": "blue", "score": 70},
+        {"name": "eve", "team": "red", "score": 88}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_top_k_descending_nulls_last() {
+    let rb = players_batch();
+    let rows = eval_rows("top_k(@, &score, 3)", &rb);
+
+    let names: Vec<&str> = rows.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["bob", "eve", "dan"]);
+}
+
+#[test]
+fn test_bottom_k_ascending() {
+    let rb = players_batch();
+    let rows = eval_rows("bottom_k(@, &score, 2)", &rb);
+
+    let names: Vec<&str> = rows.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["ann", "dan"]);
+}
+
+#[test]
+fn test_top_k_larger_than_batch_and_zero() {
+    let rb = players_batch();
+
+    let rows = eval_rows("top_k(@, &score, 100)", &rb);
+    assert_eq!(rows.len(), 5);
+    assert!(rows[4]["score"].is_null());
+
+    assert_eq!(eval_rows("top_k(@, &score, 0)", &rb).len(), 0);
+}
+
+#[test]
+fn test_top_k_after_filter_and_by_string() {
+    let rb = players_batch();
+
+    let rows = eval_rows("players[?team == 'red'] | top_k(@, &score, 1)", &rb);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["name"], "eve");
+
+    let rows = eval_rows("top_k(@, &name, 2)", &rb);
+    assert_eq!(rows[0]["name"], "eve");
+    assert_eq!(rows[1]["name"], "dan");
+}
+
+#[test]
+fn test_distinct_rows_keep_first_occurrence() {
+    let data = json!([
+        {"a": 1, "b": "x"},
+        {"a": 2, "b": "y"},
+        {"a": 1, "b": "x"},
+        {"a": 1, "b": "z"},
+        {"a": 2, "b": "y"}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    let rows = eval_rows("distinct(@)", &rb);
+    assert_eq!(rows, vec![
+        json!({"a": 1, "b": "x"}),
+        json!({"a": 2, "b": "y"}),
+        json!({"a": 1, "b": "z"}),
+    ]);
+}
+
+#[test]
+fn test_distinct_by_key() {
+    let rb = players_batch();
+
+    let rows = eval_rows("distinct_by(@, &team)", &rb);
+    let names: Vec<&str> = rows.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["ann", "bob"]);
+
+    // Best player per team: sort first, then keep the first row per key
+    let rows = eval_rows("top_k(@, &score, 5) | distinct_by(@, &team)", &rb);
+    let names: Vec<&str> = rows.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["bob", "eve"]);
+}
+
+#[test]
+#[should_panic(expected = "top_k() requires an expression reference (&field) as key")]
+fn test_top_k_requires_expr_ref() {
+    let rb = players_batch();
+    eval("top_k(@, score, 3)", &rb);
+}
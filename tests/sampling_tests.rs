@@ -0,0 +1,99 @@
+// Tests for sampling and row-limiting functions: limit, offset, sample, sample_fraction
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::array::{Array, Int64Array};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn eval_batch(query: &str, rb: &RecordBatch) -> RecordBatch {
+    match eval(query, rb) {
+        EvalResult::RecordBatch(result) => result,
+        other => panic!("Expected RecordBatch for {}, got {:?}", query, other),
+    }
+}
+
+fn ids(batch: &RecordBatch) -> Vec<i64> {
+    batch.column_by_name("id").unwrap().as_any().downcast_ref::<Int64Array>().unwrap().values().to_vec()
+}
+
+fn numbers_batch(n: i64) -> RecordBatch {
+    let data: Vec<_> = (0..n).map(|i| json!({"id": i, "even": i % 2 == 0})).collect();
+    json_to_arrow(&json!(data))
+}
+
+#[test]
+fn test_limit_and_offset() {
+    let rb = numbers_batch(10);
+
+    assert_eq!(ids(&eval_batch("limit(@, 3)", &rb)), vec![0, 1, 2]);
+    assert_eq!(ids(&eval_batch("offset(@, 7)", &rb)), vec![7, 8, 9]);
+    assert_eq!(ids(&eval_batch("limit(offset(@, 4), 2)", &rb)), vec![4, 5]);
+
+    // Counts beyond the batch are clamped
+    assert_eq!(eval_batch("limit(@, 100)", &rb).num_rows(), 10);
+    assert_eq!(eval_batch("offset(@, 100)", &rb).num_rows(), 0);
+    assert_eq!(eval_batch("limit(@, 0)", &rb).num_rows(), 0);
+}
+
+#[test]
+fn test_limit_is_zero_copy() {
+    let rb = numbers_batch(10);
+    let limited = eval_batch("offset(@, 2)", &rb);
+
+    let original = rb.column(0).to_data();
+    let sliced = limited.column(0).to_data();
+    assert_eq!(sliced.offset(), 2);
+    assert_eq!(sliced.buffers()[0].as_ptr(), original.buffers()[0].as_ptr());
+}
+
+#[test]
+fn test_limit_after_filter_and_pipe() {
+    let rb = numbers_batch(10);
+
+    let rows = arrow_to_json(&eval_batch("rows[?even] | limit(@, 2)", &rb));
+    assert_eq!(rows, json!([{"id": 0, "even": true}, {"id": 2, "even": true}]));
+}
+
+#[test]
+fn test_sample_is_deterministic_and_ordered() {
+    let rb = numbers_batch(1000);
+
+    let first = ids(&eval_batch("sample(@, 10, 42)", &rb));
+    let second = ids(&eval_batch("sample(@, 10, 42)", &rb));
+    let other_seed = ids(&eval_batch("sample(@, 10, 7)", &rb));
+
+    assert_eq!(first.len(), 10);
+    assert_eq!(first, second);
+    assert_ne!(first, other_seed);
+    assert!(first.windows(2).all(|w| w[0] < w[1]));
+
+    assert_eq!(eval_batch("sample(@, 5000, 1)", &rb).num_rows(), 1000);
+}
+
+#[test]
+fn test_sample_fraction() {
+    let rb = numbers_batch(10000);
+
+    let sampled = eval_batch("sample_fraction(@, 0.1, 3)", &rb);
+    assert!((800..1200).contains(&sampled.num_rows()), "got {} rows", sampled.num_rows());
+    assert_eq!(ids(&sampled), ids(&eval_batch("sample_fraction(@, 0.1, 3)", &rb)));
+
+    assert_eq!(eval_batch("sample_fraction(@, 0.0, 3)", &rb).num_rows(), 0);
+    assert_eq!(eval_batch("sample_fraction(@, 1, 3)", &rb).num_rows(), 10000);
+}
+
+#[test]
+#[should_panic(expected = "limit() requires a non-negative row count, got -1")]
+fn test_limit_rejects_negative_count() {
+    let rb = numbers_batch(3);
+    eval("limit(@, -1)", &rb);
+}
+
+#[test]
+#[should_panic(expected = "sample_fraction() fraction must be between 0 and 1, got 1.5")]
+fn test_sample_fraction_rejects_out_of_range() {
+    let rb = numbers_batch(3);
+    eval("sample_fraction(@, 1.5, 0)", &rb);
+}
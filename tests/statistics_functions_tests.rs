@@ -0,0 +1,143 @@
+// Tests for statistical aggregate functions: count, count_distinct, median, percentile, variance, stddev, mode, avg
+use arrow_jmespath::{json_to_arrow, EvalResult};
+use arrow::array::{Float32Array, Int32Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+fn as_int(result: EvalResult) -> i64 {
+    match result {
+        EvalResult::Int64Const(v) => v,
+        other => panic!("Expected Int64Const, got {:?}", other),
+    }
+}
+
+/// Latencies with one null row
+fn latency_batch() -> RecordBatch {
+    let data = json!([
+        {"service": "api", "latency": 10},
+        {"service": "api", "latency": 20},
+        {"service": "db", "latency": null},
+        {"service": "db", "latency": 30},
+        {"service": "api", "latency": 40}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_avg_ignores_nulls() {
+    let rb = latency_batch();
+    // (10 + 20 + 30 + 40) / 4, not / 5
+    assert_eq!(as_float(eval("avg(latency)", &rb)), 25.0);
+    assert!(matches!(eval("avg(latency[2:3])", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("avg(latency[0:0])", &rb), EvalResult::NullConst));
+}
+
+#[test]
+fn test_count_and_count_distinct() {
+    let rb = latency_batch();
+    assert_eq!(as_int(eval("count(latency)", &rb)), 4);
+    assert_eq!(as_int(eval("count(service)", &rb)), 5);
+    assert_eq!(as_int(eval("count(@)", &rb)), 5);
+    assert_eq!(as_int(eval("count_distinct(service)", &rb)), 2);
+    assert_eq!(as_int(eval("count_distinct(latency)", &rb)), 4);
+}
+
+#[test]
+fn test_median_odd_and_even() {
+    let rb = latency_batch();
+    // Non-null values: 10, 20, 30, 40
+    assert_eq!(as_float(eval("median(latency)", &rb)), 25.0);
+
+    let data = json!([{"v": 3.5}, {"v": 1.0}, {"v": 2.0}]);
+    let rb = json_to_arrow(&data);
+    assert_eq!(as_float(eval("median(v)", &rb)), 2.0);
+}
+
+#[test]
+fn test_percentile_interpolates() {
+    let rb = latency_batch();
+    assert_eq!(as_float(eval("percentile(latency, 0)", &rb)), 10.0);
+    assert_eq!(as_float(eval("percentile(latency, 1)", &rb)), 40.0);
+    // rank = 0.9 * 3 = 2.7 -> 30 + 0.7 * 10
+    assert!((as_float(eval("percentile(latency, 0.9)", &rb)) - 37.0).abs() < 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "percentile() requires 0 <= p <= 1")]
+fn test_percentile_out_of_range() {
+    let rb = latency_batch();
+    eval("percentile(latency, 95)", &rb);
+}
+
+#[test]
+fn test_variance_and_stddev() {
+    let data = json!([{"v": 2}, {"v": 4}, {"v": 4}, {"v": 4}, {"v": 5}, {"v": 5}, {"v": 7}, {"v": 9}]);
+    let rb = json_to_arrow(&data);
+    // Sample variance: 32 / 7
+    assert!((as_float(eval("variance(v)", &rb)) - 32.0 / 7.0).abs() < 1e-9);
+    assert!((as_float(eval("stddev(v)", &rb)) - (32.0f64 / 7.0).sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn test_mode_numeric_and_string() {
+    let rb = latency_batch();
+    if let EvalResult::StringConst(s) = eval("mode(service)", &rb) {
+        assert_eq!(s, "api");
+    } else {
+        panic!("Expected StringConst");
+    }
+
+    let data = json!([{"v": 3}, {"v": 1}, {"v": 3}, {"v": 1}, {"v": 2}]);
+    let rb = json_to_arrow(&data);
+    // Tie between 3 and 1: first occurrence wins
+    assert!(matches!(eval("mode(v)", &rb), EvalResult::Int64Const(3)));
+}
+
+#[test]
+fn test_aggregates_on_int32_and_float32() {
+    let schema = Schema::new(vec![
+        Field::new("i", DataType::Int32, true),
+        Field::new("f", DataType::Float32, true),
+    ]);
+    let rb = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int32Array::from(vec![Some(1), None, Some(5)])),
+            Arc::new(Float32Array::from(vec![Some(1.5), Some(2.5), None])),
+        ],
+    ).unwrap();
+
+    assert_eq!(as_float(eval("avg(i)", &rb)), 3.0);
+    assert_eq!(as_float(eval("median(f)", &rb)), 2.0);
+    assert_eq!(as_float(eval("sum(f)", &rb)), 4.0);
+    assert_eq!(as_float(eval("max(f)", &rb)), 2.5);
+    assert_eq!(as_int(eval("count(f)", &rb)), 2);
+}
+
+#[test]
+fn test_aggregates_on_filtered_batch() {
+    let rb = latency_batch();
+    if let EvalResult::RecordBatch(api) = eval("latencies[?service == 'api']", &rb) {
+        assert_eq!(as_float(eval("median(latency)", &api)), 20.0);
+        assert_eq!(as_int(eval("count(@)", &api)), 3);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+fn test_statistics_without_enough_values_are_null() {
+    let rb = latency_batch();
+    for query in [
+        "median(latency[0:0])", "median(latency[2:3])", "percentile(latency[2:3], `0.5`)",
+        "variance(latency[0:1])", "stddev(latency[0:1])", "variance(latency[2:3])", "stddev(latency[0:0])",
+    ] {
+        assert!(matches!(eval(query, &rb), EvalResult::NullConst), "{}", query);
+    }
+    assert_eq!(as_float(eval("variance(latency[0:2])", &rb)), 50.0);
+}
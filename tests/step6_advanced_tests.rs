@@ -1,4 +1,5 @@
 // Tests for Step 6: Advanced JMESPath features (@, &, sort_by, group_by)
+#![allow(unused_imports)]
 use arrow_jmespath::{parse_jmespath, eval_jmespath, json_to_arrow, arrow_to_json};
 use serde_json::json;
 
@@ -0,0 +1,91 @@
+// Tests for LargeUtf8, Utf8View and Binary columns: paths, comparisons, string functions, length and JSON output
+use arrow_jmespath::{arrow_to_json, EvalResult};
+use arrow::array::{Array, ArrayRef, BinaryArray, BinaryViewArray, Int32Array, LargeBinaryArray, LargeStringArray, StringViewArray};
+use arrow::datatypes::{Field, Schema};
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+use common::*;
+
+/// Files with names in three string layouts and contents in three binary layouts
+fn files_batch() -> RecordBatch {
+    let columns: Vec<(&str, ArrayRef)> = vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2, 3]))),
+        ("name", Arc::new(LargeStringArray::from(vec![Some("readme.md"), Some("main.rs"), None]))),
+        ("path", Arc::new(StringViewArray::from(vec![Some("/docs/readme.md"), Some("/src/main.rs"), Some("/tmp")]))),
+        ("blob", Arc::new(BinaryArray::from(vec![Some(b"abc".as_ref()), Some(b"".as_ref()), None]))),
+        ("large_blob", Arc::new(LargeBinaryArray::from(vec![Some(b"hello".as_ref()), None, Some(b"x".as_ref())]))),
+        ("view_blob", Arc::new(BinaryViewArray::from(vec![Some(b"ab".as_ref()), Some(b"abc".as_ref()), None]))),
+    ];
+    let fields: Vec<Field> = columns.iter()
+        .map(|(name, col)| Field::new(*name, col.data_type().clone(), true))
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns.into_iter().map(|(_, c)| c).collect()).unwrap()
+}
+
+#[test]
+fn test_large_and_view_strings_compare() {
+    let rb = files_batch();
+    assert_eq!(bools(eval("name == 'main.rs'", &rb)), vec![Some(false), Some(true), Some(false)]);
+    assert_eq!(bools(eval("path > '/src'", &rb)), vec![Some(false), Some(true), Some(true)]);
+    match eval("@[?name == 'readme.md'] | id", &rb) {
+        EvalResult::Int32Array(arr) => assert_eq!(arr.values().to_vec(), vec![1]),
+        other => panic!("Expected Int32Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_large_and_view_strings_in_string_functions() {
+    let rb = files_batch();
+    assert_eq!(bools(eval("ends_with(name, '.rs')", &rb)), vec![Some(false), Some(true), None]);
+    assert_eq!(bools(eval("starts_with(path, '/src')", &rb)), vec![Some(false), Some(true), Some(false)]);
+    assert_eq!(ints(eval("length(path)", &rb)), vec![Some(15), Some(12), Some(4)]);
+    match eval("path[0]", &rb) {
+        EvalResult::StringConst(s) => assert_eq!(s, "/docs/readme.md"),
+        other => panic!("Expected StringConst, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_binary_length_counts_bytes() {
+    let rb = files_batch();
+    assert_eq!(ints(eval("length(blob)", &rb)), vec![Some(3), Some(0), None]);
+    assert_eq!(ints(eval("length(large_blob)", &rb)), vec![Some(5), None, Some(1)]);
+    assert_eq!(ints(eval("length(view_blob)", &rb)), vec![Some(2), Some(3), None]);
+}
+
+#[test]
+fn test_binary_compare() {
+    let rb = files_batch();
+    assert_eq!(bools(eval("blob == 'abc'", &rb)), vec![Some(true), Some(false), Some(false)]);
+    assert_eq!(bools(eval("blob == view_blob", &rb)), vec![Some(false), Some(false), Some(true)]);
+    assert_eq!(bools(eval("view_blob < blob", &rb)), vec![Some(true), Some(false), None]);
+}
+
+#[test]
+fn test_binary_hashing() {
+    let rb = files_batch();
+    match eval("md5(large_blob)", &rb) {
+        EvalResult::StringArray(arr) => assert_eq!(arr.value(0), "5d41402abc4b2a76b9719d911017c592"),
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_json_output() {
+    let rb = files_batch();
+    assert_eq!(
+        arrow_to_json(&rb)[0],
+        json!({
+            "id": 1,
+            "name": "readme.md",
+            "path": "/docs/readme.md",
+            "blob": "YWJj",
+            "large_blob": "aGVsbG8=",
+            "view_blob": "YWI="
+        })
+    );
+    assert_eq!(arrow_to_json(&rb)[2]["name"], json!(null));
+}
@@ -0,0 +1,84 @@
+// Tests for struct columns as results: paths, nested multi-select hashes and pipes into struct fields
+use arrow_jmespath::{json_to_arrow, arrow_to_json, EvalResult};
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+fn batch(result: EvalResult) -> RecordBatch {
+    match result {
+        EvalResult::RecordBatch(batch) => batch,
+        other => panic!("Expected RecordBatch, got {:?}", other),
+    }
+}
+
+fn people_batch() -> RecordBatch {
+    json_to_arrow(&json!([
+        {"name": "Alice", "address": {"city": "Berlin", "zip": "10115"}},
+        {"name": "Bob", "address": {"city": "Hamburg", "zip": "20095"}},
+        {"name": "Carol"}
+    ]))
+}
+
+#[test]
+fn test_struct_path() {
+    let rb = people_batch();
+    match eval("address", &rb) {
+        EvalResult::StructArray(arr) => {
+            assert_eq!(arr.len(), 3);
+            assert!(arr.is_null(2));
+        },
+        other => panic!("Expected StructArray, got {:?}", other),
+    }
+    match eval("type(address)", &rb) {
+        EvalResult::StringConst(s) => assert_eq!(s, "array<struct>"),
+        other => panic!("Expected StringConst, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_struct_in_multi_select_hash() {
+    let rb = people_batch();
+    let result = batch(eval("{a: address, n: name}", &rb));
+    assert_eq!(
+        arrow_to_json(&result)[0],
+        json!({"a": {"city": "Berlin", "zip": "10115"}, "n": "Alice"})
+    );
+    assert_eq!(arrow_to_json(&result)[2], json!({"a": null, "n": "Carol"}));
+}
+
+#[test]
+fn test_nested_multi_select_hash() {
+    let rb = people_batch();
+    let result = batch(eval("{person: {n: name, c: address.city}}", &rb));
+    assert_eq!(arrow_to_json(&result)[1], json!({"person": {"n": "Bob", "c": "Hamburg"}}));
+}
+
+#[test]
+fn test_pipe_struct_into_keys_and_fields() {
+    let rb = people_batch();
+    match eval("address | keys(@)", &rb) {
+        EvalResult::StringArray(arr) => assert_eq!(arr.iter().flatten().collect::<Vec<_>>(), vec!["city", "zip"]),
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+    assert_eq!(strings(eval("keys(address)", &rb)), strings(eval("address | keys(@)", &rb)));
+    assert_eq!(strings(eval("keys(address[1])", &rb)), vec![Some("city".to_string()), Some("zip".to_string())]);
+    assert_eq!(strings(eval("keys(@)", &rb)), vec![Some("address".to_string()), Some("name".to_string())]);
+    match eval("address | city", &rb) {
+        EvalResult::StringArray(arr) => {
+            assert_eq!(arr.iter().collect::<Vec<_>>(), vec![Some("Berlin"), Some("Hamburg"), None])
+        },
+        other => panic!("Expected StringArray, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_struct_index_is_object() {
+    let rb = people_batch();
+    match eval("address[1] | zip", &rb) {
+        EvalResult::StringConst(s) => assert_eq!(s, "20095"),
+        other => panic!("Expected StringConst, got {:?}", other),
+    }
+}
@@ -0,0 +1,87 @@
+// Tests for unnest/explode of list columns into rows
+use arrow_jmespath::{json_to_arrow, EvalResult};
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use serde_json::json;
+
+mod common;
+use common::*;
+
+/// Customers with a List<Struct> orders column (one empty, one null)
+fn customers_batch() -> RecordBatch {
+    let data = json!([
+        {"customer": "ann", "orders": [{"id": 1, "amount": 30}, {"id": 2, "amount": 150}]},
+        {"customer": "bob", "orders": []},
+        {"customer": "cid", "orders": [{"id": 3, "amount": 220}]},
+        {"customer": "dan", "orders": null}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_unnest_repeats_parent_columns() {
+    let rb = customers_batch();
+    let rows = eval_rows("unnest(@, &orders)", &rb);
+
+    assert_eq!(rows, vec![
+        json!({"customer": "ann", "orders": {"id": 1, "amount": 30}}),
+        json!({"customer": "ann", "orders": {"id": 2, "amount": 150}}),
+        json!({"customer": "cid", "orders": {"id": 3, "amount": 220}}),
+    ]);
+}
+
+#[test]
+fn test_explode_keeps_empty_and_null_lists() {
+    let rb = customers_batch();
+    let rows = eval_rows("explode(@, &orders)", &rb);
+
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[2]["customer"], "bob");
+    assert!(rows[2]["orders"].is_null());
+    assert_eq!(rows[4]["customer"], "dan");
+    assert!(rows[4]["orders"].is_null());
+}
+
+#[test]
+fn test_filter_and_aggregate_at_order_granularity() {
+    let rb = customers_batch();
+
+    let rows = eval_rows("unnest(@, &orders) | orders[?orders.amount > 100]", &rb);
+    let customers: Vec<&str> = rows.iter().map(|r| r["customer"].as_str().unwrap()).collect();
+    assert_eq!(customers, vec!["ann", "cid"]);
+
+    if let EvalResult::StringArray(groups) = eval("unnest(@, &orders) | group_by(@, &customer)", &rb) {
+        assert_eq!(groups.len(), 2);
+    } else {
+        panic!("Expected StringArray");
+    }
+
+    if let EvalResult::Int64Const(total) = eval("unnest(@, &orders) | sum(orders.amount)", &rb) {
+        assert_eq!(total, 400);
+    } else {
+        panic!("Expected Int64Const");
+    }
+}
+
+#[test]
+fn test_unnest_primitive_list() {
+    let data = json!([
+        {"user": "a", "tags": ["x", "y"]},
+        {"user": "b", "tags": ["z"]}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    let rows = eval_rows("unnest(@, &tags)", &rb);
+    assert_eq!(rows, vec![
+        json!({"user": "a", "tags": "x"}),
+        json!({"user": "a", "tags": "y"}),
+        json!({"user": "b", "tags": "z"}),
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "unnest() requires a list argument")]
+fn test_unnest_rejects_non_list_column() {
+    let rb = customers_batch();
+    eval("unnest(@, &customer)", &rb);
+}
@@ -0,0 +1,144 @@
+// Tests for window functions: with_columns, row_number, rank, dense_rank, lag, lead, running_sum
+use arrow_jmespath::{parse_jmespath, json_to_arrow, EvalResult};
+use arrow::record_batch::RecordBatch;
+use serde_json::{json, Value};
+
+mod common;
+use common::*;
+
+/// Column of every row as JSON values
+fn column(rows: &[Value], name: &str) -> Vec<Value> {
+    rows.iter().map(|r| r[name].clone()).collect()
+}
+
+/// Events of two users, deliberately not sorted by timestamp
+fn events_batch() -> RecordBatch {
+    let data = json!([
+        {"user": "a", "ts": 3, "value": 30},
+        {"user": "b", "ts": 1, "value": 5},
+        {"user": "a", "ts": 1, "value": 10},
+        {"user": "a", "ts": 2, "value": null},
+        {"user": "b", "ts": 2, "value": 7}
+    ]);
+    json_to_arrow(&data)
+}
+
+#[test]
+fn test_row_number_per_partition_in_order() {
+    let rb = events_batch();
+    let rows = eval_rows("with_columns(@, {rn: row_number(&user, &ts)})", &rb);
+
+    assert_eq!(column(&rows, "rn"), vec![json!(3), json!(1), json!(1), json!(2), json!(2)]);
+    // Original columns are kept in input order
+    assert_eq!(column(&rows, "ts"), vec![json!(3), json!(1), json!(1), json!(2), json!(2)]);
+}
+
+#[test]
+fn test_rank_and_dense_rank_ties() {
+    let data = json!([
+        {"team": "x", "score": 90},
+        {"team": "x", "score": 70},
+        {"team": "x", "score": 90},
+        {"team": "x", "score": 50}
+    ]);
+    let rb = json_to_arrow(&data);
+
+    let rows = eval_rows("with_columns(@, {r: rank(&team, &score), d: dense_rank(&team, &score)})", &rb);
+    assert_eq!(column(&rows, "r"), vec![json!(3), json!(2), json!(3), json!(1)]);
+    assert_eq!(column(&rows, "d"), vec![json!(3), json!(2), json!(3), json!(1)]);
+
+    let data = json!([{"g": 1, "v": 5}, {"g": 1, "v": 5}, {"g": 1, "v": 6}]);
+    let rb = json_to_arrow(&data);
+    let rows = eval_rows("with_columns(@, {r: rank(&g, &v), d: dense_rank(&g, &v)})", &rb);
+    assert_eq!(column(&rows, "r"), vec![json!(1), json!(1), json!(3)]);
+    assert_eq!(column(&rows, "d"), vec![json!(1), json!(1), json!(2)]);
+}
+
+#[test]
+fn test_lag_and_lead_in_row_order() {
+    let rb = events_batch();
+    let rows = eval_rows("with_columns(@, {prev: lag(&value, `1`), next: lead(&value)})", &rb);
+
+    assert_eq!(column(&rows, "prev"), vec![Value::Null, json!(30), json!(5), json!(10), Value::Null]);
+    assert_eq!(column(&rows, "next"), vec![json!(5), json!(10), Value::Null, json!(7), Value::Null]);
+}
+
+#[test]
+fn test_lag_within_partition_and_order() {
+    let rb = events_batch();
+    let rows = eval_rows("with_columns(@, {prev: lag(&ts, `1`, &user, &ts), skip: lead(&ts, 2, &user, &ts)})", &rb);
+
+    assert_eq!(column(&rows, "prev"), vec![json!(2), Value::Null, Value::Null, json!(1), json!(1)]);
+    assert_eq!(column(&rows, "skip"), vec![Value::Null, Value::Null, json!(3), Value::Null, Value::Null]);
+}
+
+#[test]
+fn test_running_sum_skips_nulls() {
+    let rb = events_batch();
+    let rows = eval_rows("with_columns(@, {total: running_sum(&value, &user, &ts), all: running_sum(&value)})", &rb);
+
+    assert_eq!(column(&rows, "total"), vec![json!(40), json!(5), json!(10), json!(10), json!(12)]);
+    assert_eq!(column(&rows, "all"), vec![json!(30), json!(35), json!(45), json!(45), json!(52)]);
+
+    let data = json!([{"x": 0.5}, {"x": 0.25}]);
+    let rb = json_to_arrow(&data);
+    if let EvalResult::Float64Array(arr) = eval("running_sum(&x)", &rb) {
+        assert_eq!(arr.values().to_vec(), vec![0.5, 0.75]);
+    } else {
+        panic!("Expected Float64Array");
+    }
+}
+
+#[test]
+fn test_with_columns_replaces_and_broadcasts() {
+    let rb = events_batch();
+    let rows = eval_rows("with_columns(@, {value: abs(value), source: 'api', flag: `null`})", &rb);
+
+    let names: Vec<&String> = rows[0].as_object().unwrap().keys().collect();
+    assert_eq!(names.len(), 5);
+    assert_eq!(column(&rows, "source"), vec![json!("api"); 5]);
+    assert!(column(&rows, "flag").iter().all(Value::is_null));
+
+    // Works after a filter and feeds later stages
+    let rows = eval_rows("events[?user == 'a'] | with_columns(@, {rn: row_number(&user, &ts)}) | sort_by(@, &rn)", &rb);
+    assert_eq!(column(&rows, "ts"), vec![json!(1), json!(2), json!(3)]);
+}
+
+#[test]
+fn test_backtick_json_literals() {
+    let rb = events_batch();
+
+    assert!(matches!(eval("`42`", &rb), EvalResult::IntConst(42)));
+    assert!(matches!(eval("`true`", &rb), EvalResult::BoolConst(true)));
+    assert!(matches!(eval("`null`", &rb), EvalResult::NullConst));
+    assert!(matches!(eval("`\"a b\"`", &rb), EvalResult::StringConst(s) if s == "a b"));
+    assert!(matches!(eval("`9007199254740993`", &rb), EvalResult::Int64Const(9_007_199_254_740_993)));
+    assert!(matches!(eval("`-3000000000`", &rb), EvalResult::Int64Const(-3_000_000_000)));
+    assert!(parse_jmespath("`{\"a\": 1}`").is_err());
+    assert!(parse_jmespath("`[1, {\"a\": 1}]`").is_err());
+    if let EvalResult::FloatConst(v) = eval("`1.5e3`", &rb) {
+        assert_eq!(v, 1500.0);
+    } else {
+        panic!("Expected FloatConst");
+    }
+
+    if let EvalResult::RecordBatch(filtered) = eval("events[?ts >= `2`]", &rb) {
+        assert_eq!(filtered.num_rows(), 3);
+    } else {
+        panic!("Expected RecordBatch");
+    }
+}
+
+#[test]
+#[should_panic(expected = "lag() offset must not be negative")]
+fn test_lag_rejects_negative_offset() {
+    let rb = events_batch();
+    eval("lag(&value, -1)", &rb);
+}
+
+#[test]
+#[should_panic(expected = "running_sum() overflowed Int64")]
+fn test_running_sum_overflow_is_an_error() {
+    let rb = json_to_arrow(&json!([{"value": i64::MAX}, {"value": 1}]));
+    eval("running_sum(&value)", &rb);
+}